@@ -2,7 +2,7 @@
 //!
 //! Provides image margin detection using various algorithms.
 
-use super::types::{ContentRect, MarginDetection, MarginError, Margins, Result, TrimResult};
+use super::types::{ContentRect, FillMode, MarginDetection, MarginError, Margins, Result, TrimResult};
 use super::{ContentDetectionMode, MarginOptions};
 use image::{GenericImageView, GrayImage};
 use rayon::prelude::*;
@@ -21,6 +21,20 @@ impl ImageMarginDetector {
         }
 
         let img = image::open(image_path).map_err(|e| MarginError::InvalidImage(e.to_string()))?;
+        Self::detect_from_image(img, options)
+    }
+
+    /// Core of `detect`, operating on an already-decoded image so callers
+    /// like `split_spread` can run detection on an in-memory crop without a
+    /// round trip through disk
+    fn detect_from_image(img: image::DynamicImage, options: &MarginOptions) -> Result<MarginDetection> {
+        let (img, skew_angle) = if options.auto_deskew {
+            let angle = Self::estimate_skew_angle(&img.to_luma8(), options.background_threshold);
+            let fill = Self::corner_background_color(&img);
+            (Self::rotate_image(&img, angle, fill), Some(angle))
+        } else {
+            (img, None)
+        };
 
         let gray = img.to_luma8();
         let (width, height) = img.dimensions();
@@ -34,7 +48,8 @@ impl ImageMarginDetector {
                 Self::detect_background_margins(&gray, is_background, options)
             }
             ContentDetectionMode::EdgeDetection => Self::detect_edge_margins(&gray, options),
-            ContentDetectionMode::Histogram => Self::detect_histogram_margins(&gray, options),
+            ContentDetectionMode::Histogram => Self::detect_histogram_margins(&gray, options)?,
+            ContentDetectionMode::ColorDistance => Self::detect_color_distance_margins(&img, options),
             ContentDetectionMode::Combined => {
                 // Average of background and edge detection
                 let (t1, b1, l1, r1) =
@@ -44,11 +59,14 @@ impl ImageMarginDetector {
             }
         };
 
+        let (adj_top, adj_bottom, adj_left, adj_right) =
+            options.margin_adjustment.resolve(width, height);
+
         let margins = Margins {
-            top: top.max(options.min_margin),
-            bottom: bottom.max(options.min_margin),
-            left: left.max(options.min_margin),
-            right: right.max(options.min_margin),
+            top: Self::apply_margin_adjustment(top, adj_top, options.min_margin),
+            bottom: Self::apply_margin_adjustment(bottom, adj_bottom, options.min_margin),
+            left: Self::apply_margin_adjustment(left, adj_left, options.min_margin),
+            right: Self::apply_margin_adjustment(right, adj_right, options.min_margin),
         };
 
         let content_width = width.saturating_sub(margins.total_horizontal());
@@ -70,9 +88,378 @@ impl ImageMarginDetector {
             image_size: (width, height),
             content_rect,
             confidence: 1.0,
+            skew_angle,
         })
     }
 
+    /// Apply a resolved `Sides` delta (positive pads, negative crops
+    /// tighter) on top of a detected margin, then re-enforce `min_margin`
+    fn apply_margin_adjustment(detected: u32, delta: i32, min_margin: u32) -> u32 {
+        let adjusted = (detected as i32 + delta).max(0) as u32;
+        adjusted.max(min_margin)
+    }
+
+    /// Estimate the rotation (in degrees) needed to straighten `image_path`,
+    /// without modifying the image. Rotating by the returned angle directly
+    /// (no negation) straightens the page.
+    pub fn detect_skew(image_path: &Path, options: &MarginOptions) -> Result<f64> {
+        if !image_path.exists() {
+            return Err(MarginError::ImageNotFound(image_path.to_path_buf()));
+        }
+
+        let img = image::open(image_path).map_err(|e| MarginError::InvalidImage(e.to_string()))?;
+        Ok(Self::estimate_skew_angle(
+            &img.to_luma8(),
+            options.background_threshold,
+        ))
+    }
+
+    /// Coarse 1° search over -15..=15, refined to 0.1° around the best
+    /// coarse angle, scoring each candidate by how spiky its horizontal
+    /// projection profile becomes once rotated that far
+    fn estimate_skew_angle(gray: &GrayImage, background_threshold: u8) -> f64 {
+        const COARSE_RANGE_DEGREES: i32 = 15;
+        const REFINE_RANGE_TENTHS: i32 = 10;
+
+        // The angle search rotates the whole image once per candidate (52
+        // rotations total), which is wasted work at full resolution since a
+        // skew angle is scale-invariant - run it against a downsampled copy
+        // instead and only apply the result to the full-size image.
+        let search_image = Self::downsample_for_skew_search(gray);
+
+        let mut best_angle = 0.0;
+        let mut best_score = f64::MIN;
+        for degrees in -COARSE_RANGE_DEGREES..=COARSE_RANGE_DEGREES {
+            let angle = degrees as f64;
+            let score = Self::skew_score(&search_image, angle, background_threshold);
+            if score > best_score {
+                best_score = score;
+                best_angle = angle;
+            }
+        }
+
+        let coarse_best = best_angle;
+        for tenths in -REFINE_RANGE_TENTHS..=REFINE_RANGE_TENTHS {
+            let angle = coarse_best + tenths as f64 / 10.0;
+            let score = Self::skew_score(&search_image, angle, background_threshold);
+            if score > best_score {
+                best_score = score;
+                best_angle = angle;
+            }
+        }
+
+        best_angle
+    }
+
+    /// Longest edge, in pixels, that the skew search is allowed to rotate at.
+    /// Past this, downsampling loses negligible angle precision (a 0.1°
+    /// refine step is already coarser than what a downscaled row profile can
+    /// distinguish) while cutting the cost of each of the 52 search
+    /// rotations roughly quadratically.
+    const SKEW_SEARCH_MAX_DIM: u32 = 600;
+
+    /// Shrink `gray` so its longest edge is at most
+    /// [`SKEW_SEARCH_MAX_DIM`](Self::SKEW_SEARCH_MAX_DIM), preserving aspect
+    /// ratio; returns a clone unchanged if it's already small enough.
+    fn downsample_for_skew_search(gray: &GrayImage) -> GrayImage {
+        let (width, height) = gray.dimensions();
+        let longest_edge = width.max(height);
+        if longest_edge <= Self::SKEW_SEARCH_MAX_DIM {
+            return gray.clone();
+        }
+
+        let scale = Self::SKEW_SEARCH_MAX_DIM as f64 / longest_edge as f64;
+        let new_width = ((width as f64 * scale).round() as u32).max(1);
+        let new_height = ((height as f64 * scale).round() as u32).max(1);
+        image::imageops::resize(gray, new_width, new_height, image::imageops::FilterType::Triangle)
+    }
+
+    /// Score a candidate rotation by how spiky the resulting horizontal
+    /// projection profile is: `Σ (P(y+1) - P(y))²`. Text lines that line up
+    /// horizontally make row sums alternate sharply between "line" and
+    /// "gap", maximizing this score.
+    fn skew_score(gray: &GrayImage, angle_degrees: f64, background_threshold: u8) -> f64 {
+        let rotated = Self::rotate_gray(gray, angle_degrees, image::Luma([255]));
+        let profile = Self::row_foreground_counts(&rotated, background_threshold);
+        profile
+            .windows(2)
+            .map(|pair| {
+                let diff = pair[1] as f64 - pair[0] as f64;
+                diff * diff
+            })
+            .sum()
+    }
+
+    /// Foreground pixel count per row
+    fn row_foreground_counts(gray: &GrayImage, background_threshold: u8) -> Vec<u32> {
+        let (width, height) = gray.dimensions();
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .filter(|&x| gray.get_pixel(x, y).0[0] < background_threshold)
+                    .count() as u32
+            })
+            .collect()
+    }
+
+    /// Sample the top-left corner pixel as a simple background color estimate
+    fn corner_background_color(img: &image::DynamicImage) -> image::Rgb<u8> {
+        *img.to_rgb8().get_pixel(0, 0)
+    }
+
+    /// Foreground pixel count per column
+    fn col_foreground_counts(gray: &GrayImage, background_threshold: u8) -> Vec<u32> {
+        let (width, height) = gray.dimensions();
+        (0..width)
+            .map(|x| {
+                (0..height)
+                    .filter(|&y| gray.get_pixel(x, y).0[0] < background_threshold)
+                    .count() as u32
+            })
+            .collect()
+    }
+
+    /// Find the central gutter of a double-page spread: the local minimum of
+    /// the smoothed column profile within the central 35%-65% band of the
+    /// image width. Returns `None` when that valley isn't substantially
+    /// lower than the rest of the band, i.e. the image looks like a single
+    /// page rather than a spread.
+    fn detect_gutter_column(gray: &GrayImage, options: &MarginOptions) -> Option<u32> {
+        const BAND_LO_FRACTION: f64 = 0.35;
+        const BAND_HI_FRACTION: f64 = 0.65;
+        const VALLEY_DEPTH_FRACTION: f32 = 0.5;
+
+        let (width, _height) = gray.dimensions();
+        let col_profile = Self::col_foreground_counts(gray, options.background_threshold);
+        let smoothed = Self::moving_average(&col_profile, 5);
+
+        let band_lo = (width as f64 * BAND_LO_FRACTION) as usize;
+        let band_hi = (width as f64 * BAND_HI_FRACTION) as usize;
+        if band_hi <= band_lo || band_hi > smoothed.len() {
+            return None;
+        }
+        let band = &smoothed[band_lo..band_hi];
+
+        let (valley_offset, &valley_value) = band
+            .iter()
+            .enumerate()
+            .min_by(|a, b| a.1.partial_cmp(b.1).unwrap())?;
+
+        let band_avg: f32 = band.iter().sum::<f32>() / band.len() as f32;
+        if band_avg <= 0.0 || valley_value > band_avg * VALLEY_DEPTH_FRACTION {
+            return None;
+        }
+
+        Some((band_lo + valley_offset) as u32)
+    }
+
+    /// `_L`/`_R`-suffixed output paths for `split_spread`, derived from the
+    /// input file's own name and extension
+    fn spread_output_paths(input_path: &Path) -> (PathBuf, PathBuf) {
+        let stem = input_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("page");
+        let ext = input_path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("png");
+        let parent = input_path.parent().unwrap_or_else(|| Path::new("."));
+
+        (
+            parent.join(format!("{stem}_L.{ext}")),
+            parent.join(format!("{stem}_R.{ext}")),
+        )
+    }
+
+    /// Split a double-page spread scan into two single pages along its
+    /// central gutter, trimming each half independently. Returns `Ok(None)`
+    /// instead of splitting when the image doesn't look like a spread.
+    pub fn split_spread(input_path: &Path, options: &MarginOptions) -> Result<Option<(TrimResult, TrimResult)>> {
+        if !input_path.exists() {
+            return Err(MarginError::ImageNotFound(input_path.to_path_buf()));
+        }
+
+        let img = image::open(input_path).map_err(|e| MarginError::InvalidImage(e.to_string()))?;
+        let gray = img.to_luma8();
+
+        let Some(gutter_x) = Self::detect_gutter_column(&gray, options) else {
+            return Ok(None);
+        };
+
+        let (width, height) = img.dimensions();
+        let left_half = img.crop_imm(0, 0, gutter_x, height);
+        let right_half = img.crop_imm(gutter_x, 0, width - gutter_x, height);
+
+        let left_margins = Self::detect_from_image(left_half.clone(), options)?.margins;
+        let right_margins = Self::detect_from_image(right_half.clone(), options)?.margins;
+
+        let (left_path, right_path) = Self::spread_output_paths(input_path);
+        let left_result = Self::trim_image(&left_half, input_path, &left_path, &left_margins)?;
+        let right_result = Self::trim_image(&right_half, input_path, &right_path, &right_margins)?;
+
+        Ok(Some((left_result, right_result)))
+    }
+
+    /// Split a batch of double-page spreads, unifying the left-half margins
+    /// and the right-half margins separately across the whole batch (the
+    /// minimum detected on each side, mirroring `detect_unified`) so every
+    /// output page ends up the same trimmed size. Images that don't look
+    /// like spreads are passed through as `None` at their position.
+    pub fn split_spread_batch(
+        images: &[PathBuf],
+        options: &MarginOptions,
+    ) -> Result<Vec<Option<(TrimResult, TrimResult)>>> {
+        struct SpreadHalves {
+            input_path: PathBuf,
+            left: image::DynamicImage,
+            right: image::DynamicImage,
+            left_margins: Margins,
+            right_margins: Margins,
+        }
+
+        let per_image: Vec<Option<SpreadHalves>> = images
+            .par_iter()
+            .map(|path| -> Result<Option<SpreadHalves>> {
+                if !path.exists() {
+                    return Err(MarginError::ImageNotFound(path.clone()));
+                }
+                let img = image::open(path).map_err(|e| MarginError::InvalidImage(e.to_string()))?;
+                let gray = img.to_luma8();
+
+                let Some(gutter_x) = Self::detect_gutter_column(&gray, options) else {
+                    return Ok(None);
+                };
+
+                let (width, height) = img.dimensions();
+                let left = img.crop_imm(0, 0, gutter_x, height);
+                let right = img.crop_imm(gutter_x, 0, width - gutter_x, height);
+                let left_margins = Self::detect_from_image(left.clone(), options)?.margins;
+                let right_margins = Self::detect_from_image(right.clone(), options)?.margins;
+
+                Ok(Some(SpreadHalves {
+                    input_path: path.clone(),
+                    left,
+                    right,
+                    left_margins,
+                    right_margins,
+                }))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let unified_left = Margins {
+            top: per_image.iter().flatten().map(|s| s.left_margins.top).min().unwrap_or(0),
+            bottom: per_image.iter().flatten().map(|s| s.left_margins.bottom).min().unwrap_or(0),
+            left: per_image.iter().flatten().map(|s| s.left_margins.left).min().unwrap_or(0),
+            right: per_image.iter().flatten().map(|s| s.left_margins.right).min().unwrap_or(0),
+        };
+        let unified_right = Margins {
+            top: per_image.iter().flatten().map(|s| s.right_margins.top).min().unwrap_or(0),
+            bottom: per_image.iter().flatten().map(|s| s.right_margins.bottom).min().unwrap_or(0),
+            left: per_image.iter().flatten().map(|s| s.right_margins.left).min().unwrap_or(0),
+            right: per_image.iter().flatten().map(|s| s.right_margins.right).min().unwrap_or(0),
+        };
+
+        per_image
+            .into_iter()
+            .map(|halves| match halves {
+                None => Ok(None),
+                Some(h) => {
+                    let (left_path, right_path) = Self::spread_output_paths(&h.input_path);
+                    let left_result =
+                        Self::trim_image(&h.left, &h.input_path, &left_path, &unified_left)?;
+                    let right_result =
+                        Self::trim_image(&h.right, &h.input_path, &right_path, &unified_right)?;
+                    Ok(Some((left_result, right_result)))
+                }
+            })
+            .collect()
+    }
+
+    /// Rotate a color image by `angle_degrees` about its center, filling
+    /// pixels with no source coverage with `fill`
+    fn rotate_image(
+        img: &image::DynamicImage,
+        angle_degrees: f64,
+        fill: image::Rgb<u8>,
+    ) -> image::DynamicImage {
+        let rgb = img.to_rgb8();
+        let (width, height) = rgb.dimensions();
+        let mut out = image::RgbImage::from_pixel(width, height, fill);
+
+        Self::for_each_rotated_source(width, height, angle_degrees, |x, y, src_x, src_y| {
+            out.put_pixel(x, y, Self::bilinear_sample_rgb(&rgb, src_x, src_y));
+        });
+
+        image::DynamicImage::ImageRgb8(out)
+    }
+
+    /// Rotate a grayscale image the same way as `rotate_image`
+    fn rotate_gray(gray: &GrayImage, angle_degrees: f64, fill: image::Luma<u8>) -> GrayImage {
+        let (width, height) = gray.dimensions();
+        let mut out = GrayImage::from_pixel(width, height, fill);
+
+        Self::for_each_rotated_source(width, height, angle_degrees, |x, y, src_x, src_y| {
+            out.put_pixel(x, y, Self::bilinear_sample_gray(gray, src_x, src_y));
+        });
+
+        out
+    }
+
+    /// For every destination pixel, compute the source coordinates an
+    /// `angle_degrees` clockwise rotation (about the image center) would
+    /// sample from, and invoke `write` for those inside the source bounds
+    fn for_each_rotated_source(
+        width: u32,
+        height: u32,
+        angle_degrees: f64,
+        mut write: impl FnMut(u32, u32, f64, f64),
+    ) {
+        let angle = angle_degrees.to_radians();
+        let (sin_a, cos_a) = angle.sin_cos();
+        let cx = width as f64 / 2.0;
+        let cy = height as f64 / 2.0;
+
+        for y in 0..height {
+            for x in 0..width {
+                let dx = x as f64 - cx;
+                let dy = y as f64 - cy;
+                let src_x = cos_a * dx + sin_a * dy + cx;
+                let src_y = -sin_a * dx + cos_a * dy + cy;
+
+                if src_x >= 0.0 && src_y >= 0.0 && src_x < (width - 1) as f64 && src_y < (height - 1) as f64 {
+                    write(x, y, src_x, src_y);
+                }
+            }
+        }
+    }
+
+    fn bilinear_sample_gray(gray: &GrayImage, x: f64, y: f64) -> image::Luma<u8> {
+        let (x0, y0, fx, fy) = Self::bilinear_weights(x, y);
+        let sample = |dx: u32, dy: u32| gray.get_pixel(x0 + dx, y0 + dy).0[0] as f64;
+        let top = sample(0, 0) * (1.0 - fx) + sample(1, 0) * fx;
+        let bottom = sample(0, 1) * (1.0 - fx) + sample(1, 1) * fx;
+        image::Luma([(top * (1.0 - fy) + bottom * fy).round() as u8])
+    }
+
+    fn bilinear_sample_rgb(rgb: &image::RgbImage, x: f64, y: f64) -> image::Rgb<u8> {
+        let (x0, y0, fx, fy) = Self::bilinear_weights(x, y);
+        let mut channels = [0u8; 3];
+        for (c, channel) in channels.iter_mut().enumerate() {
+            let sample = |dx: u32, dy: u32| rgb.get_pixel(x0 + dx, y0 + dy).0[c] as f64;
+            let top = sample(0, 0) * (1.0 - fx) + sample(1, 0) * fx;
+            let bottom = sample(0, 1) * (1.0 - fx) + sample(1, 1) * fx;
+            *channel = (top * (1.0 - fy) + bottom * fy).round() as u8;
+        }
+        image::Rgb(channels)
+    }
+
+    fn bilinear_weights(x: f64, y: f64) -> (u32, u32, f64, f64) {
+        let x0 = x.floor();
+        let y0 = y.floor();
+        (x0 as u32, y0 as u32, x - x0, y - y0)
+    }
+
     /// Background color based margin detection
     fn detect_background_margins<F>(
         gray: &GrayImage,
@@ -150,30 +537,183 @@ impl ImageMarginDetector {
         0
     }
 
+    /// Color-distance based margin detection: the reference background
+    /// color is the median of small patches sampled from all four corners,
+    /// and a pixel counts as background when its perceptual distance to
+    /// that reference is within `options.color_tolerance`
+    fn detect_color_distance_margins(
+        img: &image::DynamicImage,
+        options: &MarginOptions,
+    ) -> (u32, u32, u32, u32) {
+        const CORNER_PATCH_SIZE: u32 = 12;
+
+        let rgb = img.to_rgb8();
+        let (width, height) = rgb.dimensions();
+        let reference = Self::sample_corner_background_median(&rgb, CORNER_PATCH_SIZE);
+        let tolerance = options.color_tolerance as f64;
+        let is_background =
+            |pixel: &image::Rgb<u8>| -> bool { Self::color_distance(*pixel, reference) <= tolerance };
+
+        let top = Self::find_rgb_content_start_vertical(&rgb, is_background, true);
+        // The from-the-end scans return `len - last_content_index`, so the
+        // margin itself (blank rows/cols past the content) is one less
+        let bottom = Self::find_rgb_content_start_vertical(&rgb, is_background, false).saturating_sub(1);
+        let left = Self::find_rgb_content_start_horizontal(&rgb, is_background, true);
+        let right = Self::find_rgb_content_start_horizontal(&rgb, is_background, false).saturating_sub(1);
+
+        (top, bottom, left, right)
+    }
+
+    /// Median RGB color across small patches sampled from all four corners
+    fn sample_corner_background_median(rgb: &image::RgbImage, patch: u32) -> image::Rgb<u8> {
+        let (width, height) = rgb.dimensions();
+        let patch = patch.min(width).min(height).max(1);
+
+        let corners = [
+            (0, 0),
+            (width - patch, 0),
+            (0, height - patch),
+            (width - patch, height - patch),
+        ];
+
+        let mut reds = Vec::new();
+        let mut greens = Vec::new();
+        let mut blues = Vec::new();
+        for (cx, cy) in corners {
+            for y in cy..cy + patch {
+                for x in cx..cx + patch {
+                    let pixel = rgb.get_pixel(x, y);
+                    reds.push(pixel.0[0]);
+                    greens.push(pixel.0[1]);
+                    blues.push(pixel.0[2]);
+                }
+            }
+        }
+
+        reds.sort_unstable();
+        greens.sort_unstable();
+        blues.sort_unstable();
+        let mid = reds.len() / 2;
+        image::Rgb([reds[mid], greens[mid], blues[mid]])
+    }
+
+    /// Perceptual distance between two colors in HSV space, weighting hue
+    /// and saturation (chroma) more heavily than brightness so faint gray
+    /// text on off-white paper still registers as foreground. Hue
+    /// difference is itself scaled by average saturation since hue is
+    /// unstable (and irrelevant) for near-gray colors.
+    fn color_distance(a: image::Rgb<u8>, b: image::Rgb<u8>) -> f64 {
+        const HUE_WEIGHT: f64 = 2.0;
+        const SATURATION_WEIGHT: f64 = 1.5;
+        const VALUE_WEIGHT: f64 = 1.0;
+
+        let (h1, s1, v1) = Self::rgb_to_hsv(a);
+        let (h2, s2, v2) = Self::rgb_to_hsv(b);
+
+        let hue_diff = (h1 - h2).abs();
+        let hue_diff = hue_diff.min(360.0 - hue_diff) / 180.0;
+        let avg_saturation = (s1 + s2) / 2.0;
+
+        let weighted_hue = hue_diff * avg_saturation * HUE_WEIGHT;
+        let weighted_saturation = (s1 - s2).abs() * SATURATION_WEIGHT;
+        let weighted_value = (v1 - v2).abs() * VALUE_WEIGHT;
+
+        (weighted_hue.powi(2) + weighted_saturation.powi(2) + weighted_value.powi(2)).sqrt()
+    }
+
+    /// Convert an 8-bit RGB color to (hue in degrees, saturation, value),
+    /// each of the latter two in 0.0-1.0
+    fn rgb_to_hsv(rgb: image::Rgb<u8>) -> (f64, f64, f64) {
+        let r = rgb.0[0] as f64 / 255.0;
+        let g = rgb.0[1] as f64 / 255.0;
+        let b = rgb.0[2] as f64 / 255.0;
+
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let delta = max - min;
+
+        let hue = if delta <= f64::EPSILON {
+            0.0
+        } else if max == r {
+            60.0 * (((g - b) / delta).rem_euclid(6.0))
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+
+        let saturation = if max <= f64::EPSILON { 0.0 } else { delta / max };
+        (hue, saturation, max)
+    }
+
+    /// Like `find_content_start_vertical` but classifying background by an
+    /// RGB predicate instead of luma
+    fn find_rgb_content_start_vertical<F>(rgb: &image::RgbImage, is_background: F, from_top: bool) -> u32
+    where
+        F: Fn(&image::Rgb<u8>) -> bool,
+    {
+        let (width, height) = rgb.dimensions();
+        let rows: Box<dyn Iterator<Item = u32>> = if from_top {
+            Box::new(0..height)
+        } else {
+            Box::new((0..height).rev())
+        };
+
+        for y in rows {
+            let non_bg_count = (0..width)
+                .filter(|&x| !is_background(rgb.get_pixel(x, y)))
+                .count();
+            if non_bg_count as f32 / width as f32 > 0.1 {
+                return if from_top { y } else { height - y };
+            }
+        }
+
+        0
+    }
+
+    /// Like `find_content_start_horizontal` but classifying background by an
+    /// RGB predicate instead of luma
+    fn find_rgb_content_start_horizontal<F>(rgb: &image::RgbImage, is_background: F, from_left: bool) -> u32
+    where
+        F: Fn(&image::Rgb<u8>) -> bool,
+    {
+        let (width, height) = rgb.dimensions();
+        let cols: Box<dyn Iterator<Item = u32>> = if from_left {
+            Box::new(0..width)
+        } else {
+            Box::new((0..width).rev())
+        };
+
+        for x in cols {
+            let non_bg_count = (0..height)
+                .filter(|&y| !is_background(rgb.get_pixel(x, y)))
+                .count();
+            if non_bg_count as f32 / height as f32 > 0.1 {
+                return if from_left { x } else { width - x };
+            }
+        }
+
+        0
+    }
+
     /// Edge detection based margin detection
-    fn detect_edge_margins(gray: &GrayImage, _options: &MarginOptions) -> (u32, u32, u32, u32) {
-        // Simple gradient-based edge detection
+    fn detect_edge_margins(gray: &GrayImage, options: &MarginOptions) -> (u32, u32, u32, u32) {
         let (width, height) = gray.dimensions();
+        let (magnitude, direction) = Self::sobel_gradients(gray);
+        let suppressed = Self::non_max_suppression(&magnitude, &direction, width, height);
+        let edges = Self::hysteresis(
+            &suppressed,
+            width,
+            height,
+            options.edge_low_threshold,
+            options.edge_high_threshold,
+        );
+
         let mut has_edge_row = vec![false; height as usize];
         let mut has_edge_col = vec![false; width as usize];
-
-        for y in 1..height - 1 {
-            for x in 1..width - 1 {
-                let center = gray.get_pixel(x, y).0[0] as i32;
-                let neighbors = [
-                    gray.get_pixel(x - 1, y).0[0] as i32,
-                    gray.get_pixel(x + 1, y).0[0] as i32,
-                    gray.get_pixel(x, y - 1).0[0] as i32,
-                    gray.get_pixel(x, y + 1).0[0] as i32,
-                ];
-
-                let max_diff = neighbors
-                    .iter()
-                    .map(|&n| (n - center).abs())
-                    .max()
-                    .unwrap_or(0);
-
-                if max_diff > 30 {
+        for y in 0..height {
+            for x in 0..width {
+                if edges[(y * width + x) as usize] {
                     has_edge_row[y as usize] = true;
                     has_edge_col[x as usize] = true;
                 }
@@ -199,13 +739,206 @@ impl ImageMarginDetector {
         (top, bottom, left, right)
     }
 
-    /// Histogram based margin detection
-    fn detect_histogram_margins(gray: &GrayImage, options: &MarginOptions) -> (u32, u32, u32, u32) {
-        // For now, delegate to background detection with adjusted threshold
-        let is_background = |pixel: &image::Luma<u8>| -> bool {
-            pixel.0[0] >= options.background_threshold.saturating_sub(10)
-        };
-        Self::detect_background_margins(gray, is_background, options)
+    /// Gradient magnitude and direction (radians) at every pixel, via 3x3
+    /// Sobel kernels. Border pixels are left at magnitude 0 / direction 0,
+    /// matching the old implementation's `1..height-1`/`1..width-1` interior
+    /// scan.
+    fn sobel_gradients(gray: &GrayImage) -> (Vec<f32>, Vec<f32>) {
+        let (width, height) = gray.dimensions();
+        let mut magnitude = vec![0.0f32; (width * height) as usize];
+        let mut direction = vec![0.0f32; (width * height) as usize];
+
+        if width < 3 || height < 3 {
+            return (magnitude, direction);
+        }
+
+        for y in 1..height - 1 {
+            for x in 1..width - 1 {
+                let px = |dx: i32, dy: i32| -> f32 {
+                    gray.get_pixel((x as i32 + dx) as u32, (y as i32 + dy) as u32).0[0] as f32
+                };
+
+                let gx = (px(1, -1) + 2.0 * px(1, 0) + px(1, 1))
+                    - (px(-1, -1) + 2.0 * px(-1, 0) + px(-1, 1));
+                let gy = (px(-1, 1) + 2.0 * px(0, 1) + px(1, 1))
+                    - (px(-1, -1) + 2.0 * px(0, -1) + px(1, -1));
+
+                let idx = (y * width + x) as usize;
+                magnitude[idx] = (gx * gx + gy * gy).sqrt();
+                direction[idx] = gy.atan2(gx);
+            }
+        }
+
+        (magnitude, direction)
+    }
+
+    /// Thin the Sobel magnitude map down to local maxima along the gradient
+    /// direction, binning each pixel's direction into one of four sectors
+    /// (0/45/90/135 degrees) and comparing against the two neighbors that lie
+    /// along it.
+    fn non_max_suppression(magnitude: &[f32], direction: &[f32], width: u32, height: u32) -> Vec<f32> {
+        let mut out = vec![0.0f32; magnitude.len()];
+
+        if width < 3 || height < 3 {
+            return out;
+        }
+
+        for y in 1..height - 1 {
+            for x in 1..width - 1 {
+                let idx = (y * width + x) as usize;
+                let mag = magnitude[idx];
+                if mag == 0.0 {
+                    continue;
+                }
+
+                // Normalize direction to [0, 180) degrees, perpendicular to edge.
+                let mut degrees = direction[idx].to_degrees();
+                if degrees < 0.0 {
+                    degrees += 180.0;
+                }
+
+                let (dx1, dy1, dx2, dy2): (i32, i32, i32, i32) = if !(22.5..157.5).contains(&degrees)
+                {
+                    (1, 0, -1, 0) // 0 degrees: horizontal gradient, compare left/right
+                } else if degrees < 67.5 {
+                    (1, 1, -1, -1) // 45 degrees (y grows downward)
+                } else if degrees < 112.5 {
+                    (0, 1, 0, -1) // 90 degrees: vertical gradient, compare up/down
+                } else {
+                    (-1, 1, 1, -1) // 135 degrees
+                };
+
+                let neighbor1 = magnitude[((y as i32 + dy1) as u32 * width + (x as i32 + dx1) as u32) as usize];
+                let neighbor2 = magnitude[((y as i32 + dy2) as u32 * width + (x as i32 + dx2) as u32) as usize];
+
+                if mag >= neighbor1 && mag >= neighbor2 {
+                    out[idx] = mag;
+                }
+            }
+        }
+
+        out
+    }
+
+    /// Canny-style hysteresis: pixels at/above `high` seed an 8-connected
+    /// flood fill that also promotes any connected pixel at/above `low`.
+    fn hysteresis(suppressed: &[f32], width: u32, height: u32, low: f32, high: f32) -> Vec<bool> {
+        let mut edges = vec![false; suppressed.len()];
+        let mut stack: Vec<usize> = suppressed
+            .iter()
+            .enumerate()
+            .filter(|&(_, &m)| m >= high)
+            .map(|(i, _)| i)
+            .collect();
+
+        for &idx in &stack {
+            edges[idx] = true;
+        }
+
+        while let Some(idx) = stack.pop() {
+            let x = (idx as u32) % width;
+            let y = (idx as u32) / width;
+
+            for dy in -1i32..=1 {
+                for dx in -1i32..=1 {
+                    if dx == 0 && dy == 0 {
+                        continue;
+                    }
+                    let (nx, ny) = (x as i32 + dx, y as i32 + dy);
+                    if nx < 0 || ny < 0 || nx >= width as i32 || ny >= height as i32 {
+                        continue;
+                    }
+                    let nidx = (ny as u32 * width + nx as u32) as usize;
+                    if !edges[nidx] && suppressed[nidx] >= low {
+                        edges[nidx] = true;
+                        stack.push(nidx);
+                    }
+                }
+            }
+        }
+
+        edges
+    }
+
+    /// Histogram (projection profile) based margin detection. Builds a
+    /// foreground-pixel count per row and per column, smooths each with a
+    /// moving average to suppress speckle, then finds where the smoothed
+    /// profile first/last crosses a fraction of its own peak.
+    fn detect_histogram_margins(
+        gray: &GrayImage,
+        options: &MarginOptions,
+    ) -> Result<(u32, u32, u32, u32)> {
+        let (width, height) = gray.dimensions();
+        let is_foreground =
+            |pixel: &image::Luma<u8>| -> bool { pixel.0[0] < options.background_threshold };
+
+        let mut row_profile = vec![0u32; height as usize];
+        let mut col_profile = vec![0u32; width as usize];
+        for y in 0..height {
+            for x in 0..width {
+                if is_foreground(gray.get_pixel(x, y)) {
+                    row_profile[y as usize] += 1;
+                    col_profile[x as usize] += 1;
+                }
+            }
+        }
+
+        const SMOOTHING_WINDOW: usize = 5;
+        let row_smoothed = Self::moving_average(&row_profile, SMOOTHING_WINDOW);
+        let col_smoothed = Self::moving_average(&col_profile, SMOOTHING_WINDOW);
+
+        let fraction = options.histogram_threshold_fraction;
+        let top = Self::first_crossing(&row_smoothed, fraction)
+            .ok_or(MarginError::NoContentDetected)?;
+        let bottom_last =
+            Self::last_crossing(&row_smoothed, fraction).ok_or(MarginError::NoContentDetected)?;
+        let left = Self::first_crossing(&col_smoothed, fraction)
+            .ok_or(MarginError::NoContentDetected)?;
+        let right_last =
+            Self::last_crossing(&col_smoothed, fraction).ok_or(MarginError::NoContentDetected)?;
+
+        Ok((
+            top as u32,
+            height - (bottom_last as u32 + 1),
+            left as u32,
+            width - (right_last as u32 + 1),
+        ))
+    }
+
+    /// Smooth a profile with a centered moving average of the given window
+    /// size, shrinking the window near the edges rather than padding
+    fn moving_average(profile: &[u32], window: usize) -> Vec<f32> {
+        let half = window / 2;
+        profile
+            .iter()
+            .enumerate()
+            .map(|(i, _)| {
+                let start = i.saturating_sub(half);
+                let end = (i + half + 1).min(profile.len());
+                let sum: u32 = profile[start..end].iter().sum();
+                sum as f32 / (end - start) as f32
+            })
+            .collect()
+    }
+
+    /// First index whose value exceeds `fraction` of the profile's peak
+    fn first_crossing(smoothed: &[f32], fraction: f32) -> Option<usize> {
+        let peak = smoothed.iter().cloned().fold(0.0f32, f32::max);
+        if peak <= 0.0 {
+            return None;
+        }
+        let threshold = peak * fraction;
+        smoothed.iter().position(|&v| v > threshold)
+    }
+
+    /// Last index whose value exceeds `fraction` of the profile's peak
+    fn last_crossing(smoothed: &[f32], fraction: f32) -> Option<usize> {
+        let peak = smoothed.iter().cloned().fold(0.0f32, f32::max);
+        if peak <= 0.0 {
+            return None;
+        }
+        let threshold = peak * fraction;
+        smoothed.iter().rposition(|&v| v > threshold)
     }
 
     /// Detect unified margins for multiple images
@@ -257,7 +990,16 @@ impl ImageMarginDetector {
         }
 
         let img = image::open(input_path).map_err(|e| MarginError::InvalidImage(e.to_string()))?;
+        Self::trim_image(&img, input_path, output_path, margins)
+    }
 
+    /// Core of `trim`, operating on an already-decoded image
+    fn trim_image(
+        img: &image::DynamicImage,
+        input_path: &Path,
+        output_path: &Path,
+        margins: &Margins,
+    ) -> Result<TrimResult> {
         let (width, height) = img.dimensions();
         let original_size = (width, height);
 
@@ -284,12 +1026,89 @@ impl ImageMarginDetector {
         })
     }
 
+    /// Build the `target_w` x `target_h` canvas that `pad_to_size` will later
+    /// paste the sharp original image onto at `(offset_x, offset_y)`,
+    /// filling the rest according to `fill`.
+    fn fill_padding(
+        rgb: &image::RgbImage,
+        target_w: u32,
+        target_h: u32,
+        offset_x: u32,
+        offset_y: u32,
+        fill: FillMode,
+    ) -> image::RgbImage {
+        let (width, height) = rgb.dimensions();
+
+        match fill {
+            FillMode::Solid(color) => image::RgbImage::from_pixel(target_w, target_h, image::Rgb(color)),
+            FillMode::EdgeReplicate => image::RgbImage::from_fn(target_w, target_h, |x, y| {
+                let (sx, sy) = Self::clamp_to_source(x, y, offset_x, offset_y, width, height);
+                *rgb.get_pixel(sx, sy)
+            }),
+            FillMode::Reflect => image::RgbImage::from_fn(target_w, target_h, |x, y| {
+                let (sx, sy) = Self::reflect_to_source(x, y, offset_x, offset_y, width, height);
+                *rgb.get_pixel(sx, sy)
+            }),
+            FillMode::BlurExtend => {
+                let extended = image::RgbImage::from_fn(target_w, target_h, |x, y| {
+                    let (sx, sy) = Self::clamp_to_source(x, y, offset_x, offset_y, width, height);
+                    *rgb.get_pixel(sx, sy)
+                });
+                image::imageops::blur(&extended, 12.0)
+            }
+        }
+    }
+
+    /// Map a padded-canvas coordinate to the nearest in-bounds source pixel,
+    /// clamping at the edges (used by `FillMode::EdgeReplicate`/`BlurExtend`)
+    fn clamp_to_source(
+        x: u32,
+        y: u32,
+        offset_x: u32,
+        offset_y: u32,
+        width: u32,
+        height: u32,
+    ) -> (u32, u32) {
+        let sx = (x as i64 - offset_x as i64).clamp(0, width as i64 - 1) as u32;
+        let sy = (y as i64 - offset_y as i64).clamp(0, height as i64 - 1) as u32;
+        (sx, sy)
+    }
+
+    /// Map a padded-canvas coordinate to a source pixel by reflecting across
+    /// the nearest edge of the original image (used by `FillMode::Reflect`)
+    fn reflect_to_source(
+        x: u32,
+        y: u32,
+        offset_x: u32,
+        offset_y: u32,
+        width: u32,
+        height: u32,
+    ) -> (u32, u32) {
+        let sx = Self::reflect_index(x as i64 - offset_x as i64, width);
+        let sy = Self::reflect_index(y as i64 - offset_y as i64, height);
+        (sx, sy)
+    }
+
+    /// Reflect an out-of-range index back into `0..len` as if the source
+    /// were mirrored at each boundary
+    fn reflect_index(index: i64, len: u32) -> u32 {
+        if len <= 1 {
+            return 0;
+        }
+        let period = 2 * (len as i64 - 1);
+        let mut m = index.rem_euclid(period);
+        if m >= len as i64 {
+            m = period - m;
+        }
+        m as u32
+    }
+
     /// Pad image to target size
     pub fn pad_to_size(
         input_path: &Path,
         output_path: &Path,
         target_size: (u32, u32),
-        background: [u8; 3],
+        fill: FillMode,
     ) -> Result<TrimResult> {
         if !input_path.exists() {
             return Err(MarginError::ImageNotFound(input_path.to_path_buf()));
@@ -300,18 +1119,14 @@ impl ImageMarginDetector {
         let original_size = (img.width(), img.height());
         let (target_w, target_h) = target_size;
 
-        // Create background image
-        let mut padded = image::RgbImage::new(target_w, target_h);
-        for pixel in padded.pixels_mut() {
-            *pixel = image::Rgb(background);
-        }
-
         // Center the original image
         let offset_x = (target_w.saturating_sub(img.width())) / 2;
         let offset_y = (target_h.saturating_sub(img.height())) / 2;
 
-        // Copy original image
         let rgb = img.to_rgb8();
+        let mut padded = Self::fill_padding(&rgb, target_w, target_h, offset_x, offset_y, fill);
+
+        // Copy original image over the fill, sharp
         for y in 0..img.height().min(target_h) {
             for x in 0..img.width().min(target_w) {
                 let px = x + offset_x;
@@ -364,6 +1179,7 @@ impl ImageMarginDetector {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use super::super::Sides;
 
     #[test]
     fn test_detect_nonexistent_file() {
@@ -389,8 +1205,411 @@ mod tests {
             Path::new("/nonexistent.png"),
             Path::new("/out.png"),
             (100, 100),
-            [255, 255, 255],
+            FillMode::Solid([255, 255, 255]),
+        );
+        assert!(matches!(result, Err(MarginError::ImageNotFound(_))));
+    }
+
+    #[test]
+    fn test_reflect_index_mirrors_at_boundaries() {
+        assert_eq!(ImageMarginDetector::reflect_index(0, 10), 0);
+        assert_eq!(ImageMarginDetector::reflect_index(-1, 10), 1);
+        assert_eq!(ImageMarginDetector::reflect_index(9, 10), 9);
+        assert_eq!(ImageMarginDetector::reflect_index(10, 10), 8);
+    }
+
+    #[test]
+    fn test_clamp_to_source_clamps_outside_original_bounds() {
+        let (sx, sy) = ImageMarginDetector::clamp_to_source(0, 0, 5, 5, 10, 10);
+        assert_eq!((sx, sy), (0, 0));
+        let (sx, sy) = ImageMarginDetector::clamp_to_source(20, 20, 5, 5, 10, 10);
+        assert_eq!((sx, sy), (9, 9));
+    }
+
+    #[test]
+    fn test_fill_padding_edge_replicate_extends_border_color() {
+        use image::{Rgb, RgbImage};
+
+        let rgb = RgbImage::from_pixel(4, 4, Rgb([10, 20, 30]));
+        let padded = ImageMarginDetector::fill_padding(&rgb, 10, 10, 3, 3, FillMode::EdgeReplicate);
+        assert_eq!(*padded.get_pixel(0, 0), Rgb([10, 20, 30]));
+        assert_eq!(*padded.get_pixel(9, 9), Rgb([10, 20, 30]));
+    }
+
+    #[test]
+    fn test_fill_padding_solid_fills_whole_canvas() {
+        use image::{Rgb, RgbImage};
+
+        let rgb = RgbImage::from_pixel(4, 4, Rgb([10, 20, 30]));
+        let padded =
+            ImageMarginDetector::fill_padding(&rgb, 8, 8, 2, 2, FillMode::Solid([1, 2, 3]));
+        assert_eq!(*padded.get_pixel(0, 0), Rgb([1, 2, 3]));
+        assert_eq!(*padded.get_pixel(7, 7), Rgb([1, 2, 3]));
+    }
+
+    #[test]
+    fn test_moving_average_smooths_speckle() {
+        let profile = vec![0, 10, 0, 10, 0];
+        let smoothed = ImageMarginDetector::moving_average(&profile, 5);
+        assert_eq!(smoothed.len(), profile.len());
+        assert!(smoothed[2] > 0.0);
+    }
+
+    #[test]
+    fn test_histogram_detection_on_synthetic_page() {
+        use image::{GrayImage, Luma};
+
+        let mut gray = GrayImage::from_pixel(100, 100, Luma([255]));
+        for y in 30..70 {
+            for x in 20..80 {
+                gray.put_pixel(x, y, Luma([0]));
+            }
+        }
+
+        let options = MarginOptions::default();
+        let (top, bottom, left, right) =
+            ImageMarginDetector::detect_histogram_margins(&gray, &options).unwrap();
+
+        assert!((28..=32).contains(&top), "top was {top}");
+        assert!((28..=32).contains(&bottom), "bottom was {bottom}");
+        assert!((18..=22).contains(&left), "left was {left}");
+        assert!((18..=22).contains(&right), "right was {right}");
+    }
+
+    #[test]
+    fn test_color_distance_is_zero_for_identical_colors() {
+        let color = image::Rgb([210, 190, 160]);
+        assert_eq!(ImageMarginDetector::color_distance(color, color), 0.0);
+    }
+
+    #[test]
+    fn test_color_distance_small_for_similar_sepia_shades() {
+        let background = image::Rgb([235, 222, 200]);
+        let faint_text = image::Rgb([210, 198, 178]);
+        let distance = ImageMarginDetector::color_distance(faint_text, background);
+        assert!(distance < 0.5, "distance was {distance}");
+    }
+
+    #[test]
+    fn test_color_distance_detection_on_sepia_page() {
+        use image::{DynamicImage, Rgb, RgbImage};
+
+        let background = Rgb([235, 222, 200]);
+        let mut rgb = RgbImage::from_pixel(100, 100, background);
+        for y in 30..70 {
+            for x in 20..80 {
+                rgb.put_pixel(x, y, Rgb([20, 20, 20]));
+            }
+        }
+        let img = DynamicImage::ImageRgb8(rgb);
+
+        let options = MarginOptions {
+            detection_mode: ContentDetectionMode::ColorDistance,
+            ..MarginOptions::default()
+        };
+        let (top, bottom, left, right) = ImageMarginDetector::detect_color_distance_margins(&img, &options);
+
+        assert!((25..=32).contains(&top), "top was {top}");
+        assert!((25..=32).contains(&bottom), "bottom was {bottom}");
+        assert!((15..=22).contains(&left), "left was {left}");
+        assert!((15..=22).contains(&right), "right was {right}");
+    }
+
+    #[test]
+    fn test_detect_skew_nonexistent_file() {
+        let result = ImageMarginDetector::detect_skew(
+            Path::new("/nonexistent/image.png"),
+            &MarginOptions::default(),
         );
         assert!(matches!(result, Err(MarginError::ImageNotFound(_))));
     }
+
+    #[test]
+    fn test_estimate_skew_angle_recovers_known_rotation() {
+        use image::{GrayImage, Luma};
+
+        let mut gray = GrayImage::from_pixel(200, 200, Luma([255]));
+        for y in (20..180).step_by(12) {
+            for x in 20..180 {
+                gray.put_pixel(x, y, Luma([0]));
+            }
+        }
+
+        let applied_angle = 5.0;
+        let rotated = ImageMarginDetector::rotate_gray(&gray, applied_angle, Luma([255]));
+        let estimated = ImageMarginDetector::estimate_skew_angle(&rotated, 240);
+
+        // Rotating by -applied_angle should straighten it back out, so the
+        // estimate should land close to -applied_angle.
+        assert!(
+            (estimated - (-applied_angle)).abs() < 1.5,
+            "estimated {estimated}, expected near {}",
+            -applied_angle
+        );
+    }
+
+    #[test]
+    fn test_downsample_for_skew_search_shrinks_large_images() {
+        use image::{GrayImage, Luma};
+
+        let gray = GrayImage::from_pixel(1800, 1200, Luma([255]));
+        let downsampled = ImageMarginDetector::downsample_for_skew_search(&gray);
+        let (width, height) = downsampled.dimensions();
+
+        assert_eq!(width.max(height), ImageMarginDetector::SKEW_SEARCH_MAX_DIM);
+        assert_eq!(width, 600);
+        assert_eq!(height, 400);
+    }
+
+    #[test]
+    fn test_downsample_for_skew_search_leaves_small_images_unchanged() {
+        use image::{GrayImage, Luma};
+
+        let gray = GrayImage::from_pixel(200, 150, Luma([255]));
+        let downsampled = ImageMarginDetector::downsample_for_skew_search(&gray);
+
+        assert_eq!(downsampled.dimensions(), (200, 150));
+    }
+
+    #[test]
+    fn test_rotate_gray_is_identity_at_zero_degrees() {
+        use image::{GrayImage, Luma};
+
+        let mut gray = GrayImage::from_pixel(50, 50, Luma([255]));
+        gray.put_pixel(25, 25, Luma([0]));
+
+        let rotated = ImageMarginDetector::rotate_gray(&gray, 0.0, Luma([255]));
+        assert_eq!(rotated.get_pixel(25, 25).0[0], 0);
+    }
+
+    #[test]
+    fn test_split_spread_nonexistent_file() {
+        let result = ImageMarginDetector::split_spread(
+            Path::new("/nonexistent/image.png"),
+            &MarginOptions::default(),
+        );
+        assert!(matches!(result, Err(MarginError::ImageNotFound(_))));
+    }
+
+    #[test]
+    fn test_detect_gutter_column_finds_central_valley() {
+        use image::{GrayImage, Luma};
+
+        // Two content blocks separated by a wide white gutter around x=100.
+        let mut gray = GrayImage::from_pixel(200, 100, Luma([255]));
+        for y in 10..90 {
+            for x in 10..85 {
+                gray.put_pixel(x, y, Luma([0]));
+            }
+            for x in 115..190 {
+                gray.put_pixel(x, y, Luma([0]));
+            }
+        }
+
+        let gutter = ImageMarginDetector::detect_gutter_column(&gray, &MarginOptions::default());
+        let gutter = gutter.expect("expected a gutter to be found");
+        assert!((80..=120).contains(&gutter), "gutter was {gutter}");
+    }
+
+    #[test]
+    fn test_detect_gutter_column_bails_on_single_page() {
+        use image::{GrayImage, Luma};
+
+        // Solid content block spanning the whole central band: no valley.
+        let mut gray = GrayImage::from_pixel(200, 100, Luma([255]));
+        for y in 10..90 {
+            for x in 10..190 {
+                gray.put_pixel(x, y, Luma([0]));
+            }
+        }
+
+        assert!(ImageMarginDetector::detect_gutter_column(&gray, &MarginOptions::default()).is_none());
+    }
+
+    #[test]
+    fn test_spread_output_paths_use_l_r_suffixes() {
+        let (left, right) = ImageMarginDetector::spread_output_paths(Path::new("/scans/page3.png"));
+        assert_eq!(left, Path::new("/scans/page3_L.png"));
+        assert_eq!(right, Path::new("/scans/page3_R.png"));
+    }
+
+    #[test]
+    fn test_histogram_detection_blank_image_errors() {
+        use image::{GrayImage, Luma};
+
+        let gray = GrayImage::from_pixel(50, 50, Luma([255]));
+        let result = ImageMarginDetector::detect_histogram_margins(&gray, &MarginOptions::default());
+        assert!(matches!(result, Err(MarginError::NoContentDetected)));
+    }
+
+    #[test]
+    fn test_sobel_gradients_zero_on_blank_image() {
+        use image::{GrayImage, Luma};
+
+        let gray = GrayImage::from_pixel(20, 20, Luma([128]));
+        let (magnitude, _direction) = ImageMarginDetector::sobel_gradients(&gray);
+        assert!(magnitude.iter().all(|&m| m == 0.0));
+    }
+
+    #[test]
+    fn test_sobel_gradients_fire_on_vertical_edge() {
+        use image::{GrayImage, Luma};
+
+        let mut gray = GrayImage::from_pixel(20, 20, Luma([255]));
+        for y in 0..20 {
+            for x in 10..20 {
+                gray.put_pixel(x, y, Luma([0]));
+            }
+        }
+
+        let (magnitude, _direction) = ImageMarginDetector::sobel_gradients(&gray);
+        let idx = (10 * 20 + 10) as usize;
+        assert!(magnitude[idx] > 0.0);
+    }
+
+    #[test]
+    fn test_non_max_suppression_thins_flat_ridge() {
+        // A magnitude ridge that is flat across several columns: ties along
+        // the gradient survive (>= on both sides), so the three columns of
+        // the interior row are kept, but the border rows are dropped and
+        // fewer pixels survive than went in.
+        let width = 5u32;
+        let height = 3u32;
+        let mut magnitude = vec![0.0f32; (width * height) as usize];
+        let mut direction = vec![0.0f32; (width * height) as usize];
+        for y in 0..height {
+            for x in 1..4 {
+                magnitude[(y * width + x) as usize] = 10.0;
+                direction[(y * width + x) as usize] = 0.0; // horizontal gradient
+            }
+        }
+
+        let suppressed = ImageMarginDetector::non_max_suppression(&magnitude, &direction, width, height);
+        let survivors = suppressed.iter().filter(|&&m| m > 0.0).count();
+        assert!(survivors < magnitude.iter().filter(|&&m| m > 0.0).count());
+    }
+
+    #[test]
+    fn test_hysteresis_promotes_weak_edge_connected_to_strong() {
+        let width = 3u32;
+        let height = 1u32;
+        // strong, weak-but-connected, below-low
+        let suppressed = vec![100.0, 60.0, 10.0];
+        let edges = ImageMarginDetector::hysteresis(&suppressed, width, height, 50.0, 80.0);
+        assert_eq!(edges, vec![true, true, false]);
+    }
+
+    #[test]
+    fn test_hysteresis_drops_weak_edge_without_strong_neighbor() {
+        let width = 3u32;
+        let height = 1u32;
+        let suppressed = vec![10.0, 60.0, 10.0];
+        let edges = ImageMarginDetector::hysteresis(&suppressed, width, height, 50.0, 80.0);
+        assert_eq!(edges, vec![false, false, false]);
+    }
+
+    #[test]
+    fn test_detect_edge_margins_on_synthetic_page() {
+        use image::{GrayImage, Luma};
+
+        let mut gray = GrayImage::from_pixel(100, 100, Luma([255]));
+        for y in 30..70 {
+            for x in 20..80 {
+                gray.put_pixel(x, y, Luma([0]));
+            }
+        }
+
+        let options = MarginOptions::default();
+        let (top, bottom, left, right) = ImageMarginDetector::detect_edge_margins(&gray, &options);
+        assert!((25..35).contains(&top));
+        assert!((25..35).contains(&bottom));
+        assert!((15..25).contains(&left));
+        assert!((15..25).contains(&right));
+    }
+
+    #[test]
+    fn test_apply_margin_adjustment_pads_and_crops() {
+        assert_eq!(ImageMarginDetector::apply_margin_adjustment(20, 10, 0), 30);
+        assert_eq!(ImageMarginDetector::apply_margin_adjustment(20, -10, 0), 10);
+    }
+
+    #[test]
+    fn test_apply_margin_adjustment_never_goes_negative() {
+        assert_eq!(ImageMarginDetector::apply_margin_adjustment(5, -50, 0), 0);
+    }
+
+    #[test]
+    fn test_apply_margin_adjustment_still_enforces_min_margin() {
+        assert_eq!(ImageMarginDetector::apply_margin_adjustment(5, -50, 3), 3);
+    }
+
+    #[test]
+    fn test_detect_from_image_applies_percent_margin_adjustment() {
+        use image::{DynamicImage, GrayImage, Luma};
+
+        let mut gray = GrayImage::from_pixel(100, 100, Luma([255]));
+        for y in 30..70 {
+            for x in 20..80 {
+                gray.put_pixel(x, y, Luma([0]));
+            }
+        }
+        let img = DynamicImage::ImageLuma8(gray);
+
+        let mut options = MarginOptions {
+            detection_mode: ContentDetectionMode::Histogram,
+            ..MarginOptions::default()
+        };
+        let baseline = ImageMarginDetector::detect_from_image(img.clone(), &options).unwrap();
+
+        options.margin_adjustment = Sides::uniform_percent(5.0);
+        let adjusted = ImageMarginDetector::detect_from_image(img, &options).unwrap();
+
+        assert_eq!(adjusted.margins.top, baseline.margins.top + 5);
+        assert_eq!(adjusted.margins.left, baseline.margins.left + 5);
+    }
+
+    #[test]
+    fn test_detect_from_image_with_auto_deskew_matches_unskewed_baseline() {
+        use image::{DynamicImage, GrayImage, Luma};
+
+        let mut gray = GrayImage::from_pixel(200, 200, Luma([255]));
+        for y in (20..180).step_by(12) {
+            for x in 20..180 {
+                gray.put_pixel(x, y, Luma([0]));
+            }
+        }
+
+        let options = MarginOptions {
+            detection_mode: ContentDetectionMode::Histogram,
+            auto_deskew: true,
+            ..MarginOptions::default()
+        };
+
+        let baseline =
+            ImageMarginDetector::detect_from_image(DynamicImage::ImageLuma8(gray.clone()), &options)
+                .unwrap();
+
+        let skewed = ImageMarginDetector::rotate_gray(&gray, 5.0, Luma([255]));
+        let deskewed =
+            ImageMarginDetector::detect_from_image(DynamicImage::ImageLuma8(skewed), &options)
+                .unwrap();
+
+        // auto_deskew should straighten the page back out before measuring
+        // margins, so the result should land close to the unskewed baseline
+        // rather than drifting further off (which is what happens if the
+        // rotation direction is backwards).
+        assert!(
+            (deskewed.margins.top as i64 - baseline.margins.top as i64).abs() <= 3,
+            "deskewed top {} vs baseline {}",
+            deskewed.margins.top,
+            baseline.margins.top
+        );
+        assert!(
+            (deskewed.margins.left as i64 - baseline.margins.left as i64).abs() <= 3,
+            "deskewed left {} vs baseline {}",
+            deskewed.margins.left,
+            baseline.margins.left
+        );
+        assert!(deskewed.skew_angle.is_some());
+    }
 }
@@ -0,0 +1,85 @@
+//! Margin detection and trimming module
+//!
+//! Detects the content rectangle of a scanned page (as opposed to its blank
+//! border) and crops or pads pages to a uniform size. [`ImageMarginDetector`]
+//! is the default implementation; [`MarginOptions`] selects which detection
+//! strategy it uses via [`ContentDetectionMode`].
+
+mod detect;
+mod types;
+
+pub use detect::ImageMarginDetector;
+pub use types::{
+    ContentRect, FillMode, MarginDetection, MarginDetector, MarginError, Margins, Result,
+    SideValue, Sides, TrimResult, UnifiedMargins,
+};
+
+/// Strategy used to tell page content from background
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ContentDetectionMode {
+    /// Scan rows/columns from each edge until the fraction of non-background
+    /// pixels crosses a fixed threshold
+    #[default]
+    BackgroundColor,
+    /// Look for rows/columns containing a strong local intensity gradient
+    EdgeDetection,
+    /// Smoothed per-row/per-column foreground pixel counts, thresholded at a
+    /// fraction of their own peak
+    Histogram,
+    /// Sample the background color from the image corners and classify
+    /// pixels by perceptual color distance rather than a luma threshold, so
+    /// cream/sepia paper and colored backgrounds still detect correctly
+    ColorDistance,
+    /// Average of `BackgroundColor` and `EdgeDetection`
+    Combined,
+}
+
+/// Options controlling margin detection
+#[derive(Debug, Clone)]
+pub struct MarginOptions {
+    /// Luma value at/above which a pixel counts as background
+    pub background_threshold: u8,
+    /// Margins are never reported smaller than this, even if content runs to
+    /// the edge of the image
+    pub min_margin: u32,
+    /// Which detector `detect` should use
+    pub detection_mode: ContentDetectionMode,
+    /// Fraction of a histogram profile's own peak a row/column must exceed
+    /// to count as content, used by `ContentDetectionMode::Histogram`
+    pub histogram_threshold_fraction: f32,
+    /// Detect and correct page skew before measuring margins, via
+    /// `ImageMarginDetector::detect_skew`
+    pub auto_deskew: bool,
+    /// Maximum perceptual color distance (roughly 0.0-1.0) from the sampled
+    /// background color for a pixel to still count as background, used by
+    /// `ContentDetectionMode::ColorDistance`
+    pub color_tolerance: f32,
+    /// Canny hysteresis low threshold on Sobel gradient magnitude: a pixel
+    /// below this is never an edge, used by `ContentDetectionMode::EdgeDetection`
+    pub edge_low_threshold: f32,
+    /// Canny hysteresis high threshold: a pixel at/above this is always an
+    /// edge; one between `edge_low_threshold` and this counts only if
+    /// connected to a high-threshold edge
+    pub edge_high_threshold: f32,
+    /// Inset (positive) or outset (negative) applied on top of the detected
+    /// content margins, resolved per image so a single config (e.g. "keep 3%
+    /// breathing room") produces proportionally consistent results across a
+    /// batch of differently-sized/DPI scans
+    pub margin_adjustment: Sides,
+}
+
+impl Default for MarginOptions {
+    fn default() -> Self {
+        Self {
+            background_threshold: 240,
+            min_margin: 0,
+            detection_mode: ContentDetectionMode::default(),
+            histogram_threshold_fraction: 0.1,
+            auto_deskew: false,
+            color_tolerance: 0.15,
+            edge_low_threshold: 50.0,
+            edge_high_threshold: 120.0,
+            margin_adjustment: Sides::default(),
+        }
+    }
+}
@@ -82,6 +82,9 @@ pub struct MarginDetection {
     pub content_rect: ContentRect,
     /// Detection confidence
     pub confidence: f64,
+    /// Rotation applied to straighten the page before measuring margins, in
+    /// degrees. `None` when `MarginOptions::auto_deskew` was off.
+    pub skew_angle: Option<f64>,
 }
 
 /// Unified margins result
@@ -95,6 +98,82 @@ pub struct UnifiedMargins {
     pub unified_size: (u32, u32),
 }
 
+/// A single margin value, expressed either as absolute pixels or as a
+/// percentage of the relevant image dimension
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SideValue {
+    /// An absolute number of pixels, may be negative to crop tighter
+    Pixels(i32),
+    /// A percentage (e.g. `3.0` for 3%) of the image's width or height,
+    /// may be negative to crop tighter
+    Percent(f32),
+}
+
+impl SideValue {
+    /// Resolve against the image dimension this side runs along (width for
+    /// left/right, height for top/bottom), rounding to the nearest pixel
+    pub fn resolve(self, dimension: u32) -> i32 {
+        match self {
+            SideValue::Pixels(px) => px,
+            SideValue::Percent(pct) => ((pct / 100.0) * dimension as f32).round() as i32,
+        }
+    }
+}
+
+impl Default for SideValue {
+    fn default() -> Self {
+        SideValue::Pixels(0)
+    }
+}
+
+/// Four-sided inset/outset specification, generic over absolute-pixel or
+/// percentage values via [`SideValue`]. Resolve against an image's
+/// `(width, height)` to get pixel deltas before applying to [`Margins`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Sides {
+    pub top: SideValue,
+    pub bottom: SideValue,
+    pub left: SideValue,
+    pub right: SideValue,
+}
+
+impl Sides {
+    /// All four sides set to the same percentage
+    pub fn uniform_percent(pct: f32) -> Self {
+        Self {
+            top: SideValue::Percent(pct),
+            bottom: SideValue::Percent(pct),
+            left: SideValue::Percent(pct),
+            right: SideValue::Percent(pct),
+        }
+    }
+
+    /// Resolve each side against `(width, height)`, returning signed pixel
+    /// deltas as `(top, bottom, left, right)`
+    pub fn resolve(&self, width: u32, height: u32) -> (i32, i32, i32, i32) {
+        (
+            self.top.resolve(height),
+            self.bottom.resolve(height),
+            self.left.resolve(width),
+            self.right.resolve(width),
+        )
+    }
+}
+
+/// How `pad_to_size` should fill the space added around a page
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FillMode {
+    /// Fill with a single solid RGB color
+    Solid([u8; 3]),
+    /// Extend the color of the nearest edge pixel outward
+    EdgeReplicate,
+    /// Mirror the image outward from the edge it was cropped to
+    Reflect,
+    /// Extend the edge pixels, blurred, so the padding fades rather than
+    /// producing a hard seam
+    BlurExtend,
+}
+
 /// Trim operation result
 #[derive(Debug)]
 pub struct TrimResult {
@@ -127,7 +206,7 @@ pub trait MarginDetector {
         input_path: &Path,
         output_path: &Path,
         target_size: (u32, u32),
-        background: [u8; 3],
+        fill: FillMode,
     ) -> Result<TrimResult>;
 
     /// Process batch with unified margins
@@ -170,4 +249,30 @@ mod tests {
         assert_eq!(margins.left, 0);
         assert_eq!(margins.right, 0);
     }
+
+    #[test]
+    fn test_side_value_pixels_resolve_ignores_dimension() {
+        assert_eq!(SideValue::Pixels(15).resolve(1000), 15);
+        assert_eq!(SideValue::Pixels(-5).resolve(1000), -5);
+    }
+
+    #[test]
+    fn test_side_value_percent_resolves_proportionally() {
+        assert_eq!(SideValue::Percent(10.0).resolve(200), 20);
+        assert_eq!(SideValue::Percent(-5.0).resolve(200), -10);
+    }
+
+    #[test]
+    fn test_sides_resolve_scales_top_bottom_by_height_and_left_right_by_width() {
+        let sides = Sides::uniform_percent(10.0);
+        let (top, bottom, left, right) = sides.resolve(1000, 500);
+        assert_eq!((top, bottom), (50, 50));
+        assert_eq!((left, right), (100, 100));
+    }
+
+    #[test]
+    fn test_sides_default_resolves_to_zero() {
+        let sides = Sides::default();
+        assert_eq!(sides.resolve(1000, 500), (0, 0, 0, 0));
+    }
 }
@@ -17,12 +17,18 @@
 //! ```
 
 use rayon::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashMap;
 use std::error::Error;
 use std::fmt;
+use std::fs;
+use std::marker::PhantomData;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Condvar, Mutex};
 use std::time::{Duration, Instant};
+use uuid::Uuid;
 
 /// Error type for parallel processing operations
 #[derive(Debug, Clone)]
@@ -33,6 +39,10 @@ pub enum ParallelError {
     ProcessingError { index: usize, message: String },
     /// All tasks failed
     AllTasksFailed(usize),
+    /// The batch was cancelled via an interrupt flag before it finished
+    Cancelled,
+    /// Raising the open-file-descriptor soft limit failed
+    FdLimitError(String),
 }
 
 impl fmt::Display for ParallelError {
@@ -43,12 +53,64 @@ impl fmt::Display for ParallelError {
                 write!(f, "Processing error at index {}: {}", index, message)
             }
             Self::AllTasksFailed(count) => write!(f, "All {} tasks failed", count),
+            Self::Cancelled => write!(f, "Processing was cancelled"),
+            Self::FdLimitError(msg) => write!(f, "Failed to raise file descriptor limit: {}", msg),
         }
     }
 }
 
 impl Error for ParallelError {}
 
+// ============================================================
+// File descriptor limit handling
+// ============================================================
+
+/// Batch sizes at or above this trigger an attempt to raise the soft
+/// `RLIMIT_NOFILE` before dispatching work, since small batches are
+/// unlikely to exhaust the default limit.
+const FD_LIMIT_RAISE_THRESHOLD: usize = 64;
+
+/// Raise the soft open-file-descriptor limit toward the hard limit.
+///
+/// Each parallel worker may hold several descriptors open at once (an input
+/// image, an external converter subprocess, an output file), and the
+/// default soft `RLIMIT_NOFILE` (often 256 on macOS and some Linux configs)
+/// is easy to exhaust mid-batch with an opaque "too many open files" error.
+/// No-op on Windows. Never panics; failures are returned so callers can fold
+/// them into a `ParallelError` instead.
+#[cfg(unix)]
+pub fn raise_fd_limit() -> Result<(), ParallelError> {
+    unsafe {
+        let mut limit = libc::rlimit {
+            rlim_cur: 0,
+            rlim_max: 0,
+        };
+        if libc::getrlimit(libc::RLIMIT_NOFILE, &mut limit) != 0 {
+            return Err(ParallelError::FdLimitError(
+                std::io::Error::last_os_error().to_string(),
+            ));
+        }
+
+        if limit.rlim_cur >= limit.rlim_max {
+            return Ok(());
+        }
+
+        limit.rlim_cur = limit.rlim_max;
+        if libc::setrlimit(libc::RLIMIT_NOFILE, &limit) != 0 {
+            return Err(ParallelError::FdLimitError(
+                std::io::Error::last_os_error().to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// No-op on Windows, which doesn't share Unix's small default `RLIMIT_NOFILE`.
+#[cfg(not(unix))]
+pub fn raise_fd_limit() -> Result<(), ParallelError> {
+    Ok(())
+}
+
 /// Options for parallel processing
 #[derive(Debug, Clone)]
 pub struct ParallelOptions {
@@ -58,6 +120,30 @@ pub struct ParallelOptions {
     pub chunk_size: usize,
     /// Whether to continue on errors
     pub continue_on_error: bool,
+    /// Maximum number of results the reorder buffer may hold ahead of the
+    /// consumer in `process_ordered_iter` (0 = unbounded, workers never
+    /// block on buffer space)
+    pub max_buffered: usize,
+    /// Optional cooperative cancellation flag. Checked before each item is
+    /// dispatched; once set, remaining items are reported as skipped rather
+    /// than processed. `None` means the batch always runs to completion.
+    pub should_interrupt: Option<Arc<std::sync::atomic::AtomicBool>>,
+    /// Set by `with_spill`. `process_spilled` spills each chunk's successful
+    /// results to `spill_dir` (dropping them from RAM, so peak memory stays
+    /// at roughly one chunk) whether or not this is set; `process` and the
+    /// other methods never spill and do not read it.
+    pub spill: bool,
+    /// Where `process_spilled` writes its spill files. `None` defaults to the
+    /// system temp directory, mirroring the tempdir parameter of
+    /// GNU-parallel-style tools; set it to point at fast scratch storage.
+    pub spill_dir: Option<PathBuf>,
+    /// Whether `process` should try to raise the soft open-file-descriptor
+    /// limit toward the hard limit before dispatching a large batch.
+    /// Defaults to on, since the default soft limit (often 256 on macOS) is
+    /// easy to exhaust once each worker may have an input image, an
+    /// external converter process, and an output file open at once; set to
+    /// `false` if the embedder already manages `RLIMIT_NOFILE` itself.
+    pub raise_fd_limit: bool,
 }
 
 impl Default for ParallelOptions {
@@ -66,6 +152,11 @@ impl Default for ParallelOptions {
             num_threads: 0,
             chunk_size: 0,
             continue_on_error: true,
+            max_buffered: 0,
+            should_interrupt: None,
+            spill: false,
+            spill_dir: None,
+            raise_fd_limit: true,
         }
     }
 }
@@ -87,6 +178,50 @@ impl ParallelOptions {
         }
     }
 
+    /// Create options with a bounded reorder buffer for `process_ordered_iter`
+    pub fn with_max_buffered(max_buffered: usize) -> Self {
+        Self {
+            max_buffered,
+            ..Default::default()
+        }
+    }
+
+    /// Create options wired to the given interrupt flag, for callers that
+    /// manage their own signal handling (e.g. a GUI "stop" button) and just
+    /// want to flip an `AtomicBool` when the user asks to abort.
+    pub fn with_interrupt(should_interrupt: Arc<std::sync::atomic::AtomicBool>) -> Self {
+        Self {
+            should_interrupt: Some(should_interrupt),
+            ..Default::default()
+        }
+    }
+
+    /// Create options that spill successful results to disk instead of
+    /// accumulating them in memory; `spill_dir` overrides the system temp
+    /// directory as the spill location.
+    pub fn with_spill(spill_dir: Option<PathBuf>) -> Self {
+        Self {
+            spill: true,
+            spill_dir,
+            ..Default::default()
+        }
+    }
+
+    /// Create options with a fresh interrupt flag and a process-wide Ctrl-C
+    /// handler already installed to set it. Returns the flag alongside the
+    /// options so the caller can also trip it programmatically (e.g. from a
+    /// GUI "stop" button). Library consumers who manage their own signals
+    /// should use [`with_interrupt`](Self::with_interrupt) instead, which
+    /// does not touch process-wide signal handling.
+    pub fn with_ctrlc_handler() -> (Self, Arc<std::sync::atomic::AtomicBool>) {
+        let flag = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let handler_flag = Arc::clone(&flag);
+        let _ = ctrlc::set_handler(move || {
+            handler_flag.store(true, Ordering::Relaxed);
+        });
+        (Self::with_interrupt(Arc::clone(&flag)), flag)
+    }
+
     /// Get effective thread count
     pub fn effective_threads(&self) -> usize {
         if self.num_threads == 0 {
@@ -108,6 +243,12 @@ pub struct ParallelResult<T> {
     pub duration: Duration,
     /// Number of items processed
     pub processed_count: usize,
+    /// Whether the batch was aborted via an interrupt flag before every
+    /// item was dispatched
+    pub cancelled: bool,
+    /// Number of items skipped because the interrupt flag was already set
+    /// when their turn came up
+    pub skipped_count: usize,
 }
 
 impl<T> ParallelResult<T> {
@@ -131,13 +272,436 @@ impl<T> ParallelResult<T> {
     }
 }
 
+/// Result of a parallel fold produced by [`ParallelProcessor::reduce`]
+#[derive(Debug)]
+pub struct ReduceResult<T> {
+    /// The final, merged accumulator
+    pub accumulator: T,
+    /// Errors with their indices and messages
+    pub errors: Vec<(usize, String)>,
+    /// Total processing duration
+    pub duration: Duration,
+    /// Number of items processed
+    pub processed_count: usize,
+}
+
+impl<T> ReduceResult<T> {
+    /// Check if all items were folded successfully
+    pub fn is_success(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Owns a spill directory and removes it on drop. Lets [`SpilledResult`]
+/// clean up even if the caller never calls
+/// [`ordered_results`](SpilledResult::ordered_results); [`into_path`](Self::into_path)
+/// hands the path to [`SpilledResultsIter`] without running that cleanup early.
+#[derive(Debug)]
+struct SpillGuard(PathBuf);
+
+impl SpillGuard {
+    fn into_path(self) -> PathBuf {
+        let path = self.0.clone();
+        std::mem::forget(self);
+        path
+    }
+}
+
+impl Drop for SpillGuard {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Result of a disk-spilled parallel batch produced by
+/// [`ParallelProcessor::process_spilled`]. Successful results live on disk
+/// under a per-run spill directory keyed by input index rather than in this
+/// struct; call [`ordered_results`](Self::ordered_results) to stream them
+/// back. If the caller never does, the spill directory is still removed
+/// when this struct is dropped.
+#[derive(Debug)]
+pub struct SpilledResult<T> {
+    spill_root: SpillGuard,
+    /// Errors with their indices and messages
+    pub errors: Vec<(usize, String)>,
+    /// Total processing duration
+    pub duration: Duration,
+    /// Number of items processed
+    pub processed_count: usize,
+    total: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T> SpilledResult<T> {
+    /// Check if all items were processed successfully
+    pub fn is_success(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+impl<T: DeserializeOwned> SpilledResult<T> {
+    /// Stream successful results back in original input order, deserializing
+    /// (and deleting) one spilled file at a time so reading them back stays
+    /// O(1) in memory regardless of batch size. The spill directory itself
+    /// is removed once the iterator is dropped.
+    pub fn ordered_results(self) -> SpilledResultsIter<T> {
+        SpilledResultsIter {
+            spill_root: self.spill_root.into_path(),
+            next: 0,
+            total: self.total,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Iterator streaming spilled results back from disk in order. See
+/// [`SpilledResult::ordered_results`].
+pub struct SpilledResultsIter<T> {
+    spill_root: PathBuf,
+    next: usize,
+    total: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T: DeserializeOwned> Iterator for SpilledResultsIter<T> {
+    type Item = Result<T, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next < self.total {
+            let idx = self.next;
+            self.next += 1;
+
+            let file_path = self.spill_root.join(format!("{}.json", idx));
+            if !file_path.exists() {
+                // This index errored during processing; no file was spilled.
+                continue;
+            }
+
+            let result = fs::File::open(&file_path)
+                .map_err(|e| e.to_string())
+                .and_then(|f| serde_json::from_reader(f).map_err(|e| e.to_string()));
+            let _ = fs::remove_file(&file_path);
+            return Some(result);
+        }
+        None
+    }
+}
+
+impl<T> Drop for SpilledResultsIter<T> {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.spill_root);
+    }
+}
+
 /// Progress callback type
 pub type ProgressCallback = Arc<dyn Fn(usize, usize) + Send + Sync>;
 
+/// Per-item log-line callback type: `(index, line)`
+pub type LogLineCallback = Arc<dyn Fn(usize, &str) + Send + Sync>;
+
+// ============================================================
+// Subprocess output capture
+// ============================================================
+
+/// Accumulates a child process's stdout/stderr and the line boundaries
+/// found in it so far, without losing partial lines between reads.
+struct LineDrain {
+    /// Raw bytes not yet decoded into `acc` — may end in a multi-byte UTF-8
+    /// sequence that a non-blocking read split in two, in which case it's
+    /// held here until the rest of the sequence arrives.
+    raw: Vec<u8>,
+    acc: String,
+    flushed: usize,
+}
+
+impl LineDrain {
+    fn new() -> Self {
+        Self {
+            raw: Vec::new(),
+            acc: String::new(),
+            flushed: 0,
+        }
+    }
+
+    fn push(&mut self, bytes: &[u8]) {
+        self.raw.extend_from_slice(bytes);
+        self.decode_available();
+    }
+
+    /// Decode as much of `raw` as is valid (or definitely-invalid) UTF-8,
+    /// appending it to `acc`. A trailing sequence that's merely incomplete
+    /// so far is left in `raw` for the next `push` to complete, instead of
+    /// being decoded (and corrupted into replacement characters) on its own.
+    fn decode_available(&mut self) {
+        let decode_to = match std::str::from_utf8(&self.raw) {
+            Ok(_) => self.raw.len(),
+            Err(e) => match e.error_len() {
+                // A genuinely invalid sequence, not just a truncated one;
+                // decode through it so bad bytes don't stall the stream.
+                Some(invalid_len) => e.valid_up_to() + invalid_len,
+                None => e.valid_up_to(),
+            },
+        };
+        if decode_to == 0 {
+            return;
+        }
+        self.acc.push_str(&String::from_utf8_lossy(&self.raw[..decode_to]));
+        self.raw.drain(..decode_to);
+    }
+
+    /// Invoke `on_line` for every complete line accumulated so far.
+    fn flush_complete_lines(&mut self, mut on_line: impl FnMut(&str)) {
+        while let Some(rel_newline) = self.acc[self.flushed..].find('\n') {
+            let line_end = self.flushed + rel_newline;
+            let line = self.acc[self.flushed..line_end].trim_end_matches('\r').to_string();
+            on_line(&line);
+            self.flushed = line_end + 1;
+        }
+    }
+
+    /// Invoke `on_line` for a trailing line with no terminator, if any. Also
+    /// decodes any bytes still held back as a possibly-incomplete sequence,
+    /// since no more reads are coming once this is called.
+    fn flush_trailing(&mut self, mut on_line: impl FnMut(&str)) {
+        if !self.raw.is_empty() {
+            self.acc.push_str(&String::from_utf8_lossy(&self.raw));
+            self.raw.clear();
+        }
+        if self.flushed < self.acc.len() {
+            let line = self.acc[self.flushed..].to_string();
+            self.flushed = self.acc.len();
+            if !line.is_empty() {
+                on_line(&line);
+            }
+        }
+    }
+}
+
+/// Captured output from a child process run via [`CommandOutput::run`].
+#[derive(Debug, Clone)]
+pub struct CommandOutput {
+    /// Full captured standard output
+    pub stdout: String,
+    /// Full captured standard error
+    pub stderr: String,
+    /// Process exit code, if the process exited normally
+    pub status: Option<i32>,
+}
+
+impl CommandOutput {
+    /// Run `command`, draining its stdout/stderr incrementally instead of
+    /// letting a full pipe buffer deadlock the child — a real risk for
+    /// chatty tools like Ghostscript or an OCR binary running under a
+    /// parallel worker. Each complete line is reported to `on_line` as soon
+    /// as it's available (so a caller can forward it to a per-page log
+    /// callback live), and the full captured output is still returned once
+    /// the process exits.
+    #[cfg(unix)]
+    pub fn run(command: &mut std::process::Command, mut on_line: impl FnMut(&str)) -> std::io::Result<Self> {
+        use std::io::Read;
+        use std::os::unix::io::AsRawFd;
+
+        let mut child = command
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()?;
+
+        let mut stdout_pipe = child.stdout.take();
+        let mut stderr_pipe = child.stderr.take();
+
+        if let Some(ref f) = stdout_pipe {
+            set_nonblocking(f.as_raw_fd());
+        }
+        if let Some(ref f) = stderr_pipe {
+            set_nonblocking(f.as_raw_fd());
+        }
+
+        let mut stdout_drain = LineDrain::new();
+        let mut stderr_drain = LineDrain::new();
+        let mut buf = [0u8; 4096];
+
+        while stdout_pipe.is_some() || stderr_pipe.is_some() {
+            let mut made_progress = false;
+
+            if let Some(ref mut pipe) = stdout_pipe {
+                match pipe.read(&mut buf) {
+                    Ok(0) => stdout_pipe = None,
+                    Ok(n) => {
+                        stdout_drain.push(&buf[..n]);
+                        made_progress = true;
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {}
+                    Err(e) => return Err(e),
+                }
+            }
+            stdout_drain.flush_complete_lines(&mut on_line);
+
+            if let Some(ref mut pipe) = stderr_pipe {
+                match pipe.read(&mut buf) {
+                    Ok(0) => stderr_pipe = None,
+                    Ok(n) => {
+                        stderr_drain.push(&buf[..n]);
+                        made_progress = true;
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {}
+                    Err(e) => return Err(e),
+                }
+            }
+            stderr_drain.flush_complete_lines(&mut on_line);
+
+            if !made_progress && (stdout_pipe.is_some() || stderr_pipe.is_some()) {
+                std::thread::sleep(std::time::Duration::from_millis(5));
+            }
+        }
+
+        let status = child.wait()?;
+        stdout_drain.flush_trailing(&mut on_line);
+        stderr_drain.flush_trailing(&mut on_line);
+
+        Ok(Self {
+            stdout: stdout_drain.acc,
+            stderr: stderr_drain.acc,
+            status: status.code(),
+        })
+    }
+
+    /// Non-Unix fallback: non-blocking pipe polling relies on `fcntl`, so
+    /// here we just wait for the whole process and report its output after
+    /// the fact rather than incrementally.
+    #[cfg(not(unix))]
+    pub fn run(command: &mut std::process::Command, mut on_line: impl FnMut(&str)) -> std::io::Result<Self> {
+        let output = command.output()?;
+        let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+        let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+        for line in stdout.lines().chain(stderr.lines()) {
+            on_line(line);
+        }
+        Ok(Self {
+            stdout,
+            stderr,
+            status: output.status.code(),
+        })
+    }
+}
+
+#[cfg(unix)]
+fn set_nonblocking(fd: std::os::unix::io::RawFd) {
+    unsafe {
+        let flags = libc::fcntl(fd, libc::F_GETFL, 0);
+        if flags >= 0 {
+            libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK);
+        }
+    }
+}
+
+/// Shared state backing a [`ReorderBuffer`]
+struct ReorderState<T> {
+    buffer: HashMap<usize, Result<T, String>>,
+    outstanding: usize,
+    total: usize,
+    cancelled: bool,
+}
+
+/// Bounded reorder buffer shared between the rayon workers producing
+/// out-of-order results and the consumer iterator reading them in order.
+struct ReorderBuffer<T> {
+    state: Mutex<ReorderState<T>>,
+    space_available: Condvar,
+    item_ready: Condvar,
+}
+
+impl<T> ReorderBuffer<T> {
+    fn new(total: usize) -> Self {
+        Self {
+            state: Mutex::new(ReorderState {
+                buffer: HashMap::new(),
+                outstanding: 0,
+                total,
+                cancelled: false,
+            }),
+            space_available: Condvar::new(),
+            item_ready: Condvar::new(),
+        }
+    }
+
+    /// Reserve a slot in the window before processing an item. Blocks while
+    /// the window is full; returns `false` if the buffer was cancelled (the
+    /// consumer was dropped before consuming everything) and the caller
+    /// should stop producing.
+    fn acquire_slot(&self, max_buffered: usize) -> bool {
+        let mut state = self.state.lock().unwrap();
+        while !state.cancelled && max_buffered != 0 && state.outstanding >= max_buffered {
+            state = self.space_available.wait(state).unwrap();
+        }
+        if state.cancelled {
+            return false;
+        }
+        state.outstanding += 1;
+        true
+    }
+
+    /// Publish a completed result, waking the consumer if it is waiting on it.
+    fn publish(&self, index: usize, result: Result<T, String>) {
+        let mut state = self.state.lock().unwrap();
+        state.buffer.insert(index, result);
+        self.item_ready.notify_all();
+    }
+}
+
+/// Iterator yielding `Result<T, String>` in original input order as soon as
+/// the next expected item becomes available. Returned by
+/// [`ParallelProcessor::process_ordered_iter`].
+pub struct OrderedResults<T> {
+    shared: Arc<ReorderBuffer<T>>,
+    yielded: usize,
+    worker: Option<std::thread::JoinHandle<()>>,
+}
+
+impl<T> Iterator for OrderedResults<T> {
+    type Item = Result<T, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut state = self.shared.state.lock().unwrap();
+        if self.yielded >= state.total {
+            return None;
+        }
+
+        loop {
+            if let Some(result) = state.buffer.remove(&self.yielded) {
+                self.yielded += 1;
+                state.outstanding = state.outstanding.saturating_sub(1);
+                drop(state);
+                self.shared.space_available.notify_one();
+                return Some(result);
+            }
+            state = self.shared.item_ready.wait(state).unwrap();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.shared.state.lock().unwrap().total - self.yielded;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T> Drop for OrderedResults<T> {
+    fn drop(&mut self) {
+        {
+            let mut state = self.shared.state.lock().unwrap();
+            state.cancelled = true;
+        }
+        self.shared.space_available.notify_all();
+        if let Some(handle) = self.worker.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
 /// Parallel processor for batch operations
 pub struct ParallelProcessor {
     options: ParallelOptions,
     progress_callback: Option<ProgressCallback>,
+    log_callback: Option<LogLineCallback>,
 }
 
 impl ParallelProcessor {
@@ -146,6 +710,7 @@ impl ParallelProcessor {
         Self {
             options: ParallelOptions::default(),
             progress_callback: None,
+            log_callback: None,
         }
     }
 
@@ -154,6 +719,7 @@ impl ParallelProcessor {
         Self {
             options,
             progress_callback: None,
+            log_callback: None,
         }
     }
 
@@ -166,6 +732,18 @@ impl ParallelProcessor {
         self
     }
 
+    /// Set a per-item log-line callback, invoked as `(index, line)` for each
+    /// line of subprocess output a `process_with_logging` closure reports via
+    /// its `CommandOutput`, so callers can stream per-page tool diagnostics
+    /// live instead of only seeing them once the whole batch finishes.
+    pub fn with_log_callback<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(usize, &str) + Send + Sync + 'static,
+    {
+        self.log_callback = Some(Arc::new(callback));
+        self
+    }
+
     /// Process items in parallel
     pub fn process<T, E, F>(&self, items: &[PathBuf], processor: F) -> ParallelResult<T>
     where
@@ -182,11 +760,21 @@ impl ParallelProcessor {
                 errors: vec![],
                 duration: Duration::ZERO,
                 processed_count: 0,
+                cancelled: false,
+                skipped_count: 0,
             };
         }
 
+        if self.options.raise_fd_limit && total >= FD_LIMIT_RAISE_THRESHOLD {
+            // Best-effort: a failure here shouldn't abort an otherwise-fine
+            // batch, it just means the caller may hit "too many open files"
+            // later if the default soft limit really was too low.
+            let _ = raise_fd_limit();
+        }
+
         let completed = Arc::new(AtomicUsize::new(0));
         let progress_callback = self.progress_callback.clone();
+        let should_interrupt = self.options.should_interrupt.clone();
 
         // Build thread pool if custom thread count specified
         let pool = if self.options.num_threads > 0 {
@@ -198,10 +786,18 @@ impl ParallelProcessor {
             None
         };
 
-        let process_chunk = |chunk: &[(usize, &PathBuf)]| -> Vec<(usize, Result<T, String>)> {
+        let process_chunk = |chunk: &[(usize, &PathBuf)]| -> Vec<(usize, Option<Result<T, String>>)> {
             chunk
                 .par_iter()
                 .map(|(idx, path)| {
+                    // Short-circuit to a skipped state once cancellation has
+                    // been requested, rather than starting more work.
+                    if let Some(ref flag) = should_interrupt {
+                        if flag.load(Ordering::Relaxed) {
+                            return (*idx, None);
+                        }
+                    }
+
                     let result = processor(path).map_err(|e| e.to_string());
 
                     // Update progress
@@ -210,7 +806,7 @@ impl ParallelProcessor {
                         cb(done, total);
                     }
 
-                    (*idx, result)
+                    (*idx, Some(result))
                 })
                 .collect()
         };
@@ -238,86 +834,518 @@ impl ParallelProcessor {
             }
         };
 
-        // Separate successes and errors
+        // Separate successes, errors and skipped items
         let mut results = Vec::new();
         let mut errors = Vec::new();
+        let mut skipped_count = 0;
 
         for (idx, result) in all_results {
             match result {
-                Ok(value) => results.push((idx, value)),
-                Err(msg) => errors.push((idx, msg)),
+                Some(Ok(value)) => results.push((idx, value)),
+                Some(Err(msg)) => errors.push((idx, msg)),
+                None => skipped_count += 1,
             }
         }
 
+        let cancelled = should_interrupt
+            .map(|flag| flag.load(Ordering::Relaxed))
+            .unwrap_or(false);
+
         ParallelResult {
             results,
             errors,
             duration: start.elapsed(),
             processed_count: total,
+            cancelled,
+            skipped_count,
         }
     }
 
-    /// Process items with a simple function (no error handling)
-    pub fn map<T, F>(&self, items: &[PathBuf], mapper: F) -> Vec<T>
+    /// Process items in parallel, giving each item's closure a per-item log
+    /// sink instead of letting subprocess output interleave on the terminal.
+    ///
+    /// `processor` receives `(&Path, &dyn Fn(&str))`; forward lines captured
+    /// via [`CommandOutput::run`] to that sink and they arrive tagged with
+    /// this item's index, both through the live `log_callback`
+    /// (`with_log_callback`) and attached to that index's `(index, message)`
+    /// entry in the returned errors when the item itself fails.
+    pub fn process_with_logging<T, E, F>(&self, items: &[PathBuf], processor: F) -> ParallelResult<T>
     where
-        F: Fn(&Path) -> T + Sync + Send,
+        F: Fn(&Path, &dyn Fn(&str)) -> Result<T, E> + Sync + Send,
+        E: std::fmt::Display,
         T: Send,
     {
-        if self.options.num_threads > 0 {
-            if let Ok(pool) = rayon::ThreadPoolBuilder::new()
-                .num_threads(self.options.num_threads)
-                .build()
-            {
-                return pool.install(|| items.par_iter().map(|p| mapper(p)).collect());
-            }
+        let start = Instant::now();
+        let total = items.len();
+
+        if total == 0 {
+            return ParallelResult {
+                results: vec![],
+                errors: vec![],
+                duration: Duration::ZERO,
+                processed_count: 0,
+                cancelled: false,
+                skipped_count: 0,
+            };
         }
 
-        items.par_iter().map(|p| mapper(p)).collect()
-    }
-}
+        if self.options.raise_fd_limit && total >= FD_LIMIT_RAISE_THRESHOLD {
+            let _ = raise_fd_limit();
+        }
 
-impl Default for ParallelProcessor {
-    fn default() -> Self {
-        Self::new()
-    }
-}
+        let completed = Arc::new(AtomicUsize::new(0));
+        let progress_callback = self.progress_callback.clone();
+        let log_callback = self.log_callback.clone();
+        let should_interrupt = self.options.should_interrupt.clone();
 
-/// Convenience function for parallel processing
-pub fn parallel_process<T, E, F>(
-    inputs: &[PathBuf],
-    processor: F,
-    options: &ParallelOptions,
-) -> ParallelResult<T>
-where
-    F: Fn(&Path) -> Result<T, E> + Sync + Send,
-    E: std::fmt::Display,
-    T: Send,
-{
-    ParallelProcessor::with_options(options.clone()).process(inputs, processor)
-}
+        let pool = if self.options.num_threads > 0 {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(self.options.num_threads)
+                .build()
+                .ok()
+        } else {
+            None
+        };
 
-/// Parallel map with simple function
-pub fn parallel_map<T, F>(inputs: &[PathBuf], mapper: F, num_threads: usize) -> Vec<T>
-where
-    F: Fn(&Path) -> T + Sync + Send,
-    T: Send,
-{
-    ParallelProcessor::with_options(ParallelOptions::with_threads(num_threads)).map(inputs, mapper)
-}
+        let process_chunk = |chunk: &[(usize, &PathBuf)]| -> Vec<(usize, Option<Result<T, String>>)> {
+            chunk
+                .par_iter()
+                .map(|(idx, path)| {
+                    if let Some(ref flag) = should_interrupt {
+                        if flag.load(Ordering::Relaxed) {
+                            return (*idx, None);
+                        }
+                    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs::File;
-    use std::io::Write;
-    use tempfile::tempdir;
+                    let idx = *idx;
+                    let captured: Mutex<Vec<String>> = Mutex::new(Vec::new());
+                    let sink = |line: &str| {
+                        captured.lock().unwrap().push(line.to_string());
+                        if let Some(ref cb) = log_callback {
+                            cb(idx, line);
+                        }
+                    };
+
+                    let result = processor(path, &sink).map_err(|e| {
+                        let lines = captured.lock().unwrap();
+                        if lines.is_empty() {
+                            e.to_string()
+                        } else {
+                            format!("{}\n{}", e, lines.join("\n"))
+                        }
+                    });
 
-    // ============ TC PAR-001: Basic parallel processing ============
+                    let done = completed.fetch_add(1, Ordering::Relaxed) + 1;
+                    if let Some(ref cb) = progress_callback {
+                        cb(done, total);
+                    }
 
-    #[test]
-    fn test_par001_parallel_process_basic() {
-        let dir = tempdir().unwrap();
-        let paths: Vec<PathBuf> = (0..10)
+                    (idx, Some(result))
+                })
+                .collect()
+        };
+
+        let indexed_items: Vec<_> = items.iter().enumerate().collect();
+        let chunk_size = if self.options.chunk_size > 0 {
+            self.options.chunk_size
+        } else {
+            total
+        };
+
+        let mut all_results = Vec::with_capacity(total);
+        for chunk in indexed_items.chunks(chunk_size) {
+            let chunk_results = if let Some(ref pool) = pool {
+                pool.install(|| process_chunk(chunk))
+            } else {
+                process_chunk(chunk)
+            };
+            all_results.extend(chunk_results);
+        }
+
+        let mut results = Vec::new();
+        let mut errors = Vec::new();
+        let mut skipped_count = 0;
+
+        for (idx, result) in all_results {
+            match result {
+                Some(Ok(value)) => results.push((idx, value)),
+                Some(Err(msg)) => errors.push((idx, msg)),
+                None => skipped_count += 1,
+            }
+        }
+
+        let cancelled = should_interrupt
+            .map(|flag| flag.load(Ordering::Relaxed))
+            .unwrap_or(false);
+
+        ParallelResult {
+            results,
+            errors,
+            duration: start.elapsed(),
+            processed_count: total,
+            cancelled,
+            skipped_count,
+        }
+    }
+
+    /// Process items in parallel, spilling each chunk's successful results
+    /// to disk instead of accumulating them in a `Vec<(usize, T)>`.
+    ///
+    /// Peak memory stays at roughly one chunk regardless of total item
+    /// count, which matters when `T` is e.g. a decoded full-resolution
+    /// image for a multi-thousand-page book. Results are written under
+    /// `ParallelOptions::spill_dir` (the system temp dir by default) keyed
+    /// by input index, and streamed back in order by
+    /// [`SpilledResult::ordered_results`], which deserializes (and deletes)
+    /// one file at a time.
+    pub fn process_spilled<T, E, F>(&self, items: &[PathBuf], processor: F) -> SpilledResult<T>
+    where
+        F: Fn(&Path) -> Result<T, E> + Sync + Send,
+        E: std::fmt::Display,
+        T: Send + Serialize,
+    {
+        let start = Instant::now();
+        let total = items.len();
+
+        let spill_root = self
+            .options
+            .spill_dir
+            .clone()
+            .unwrap_or_else(std::env::temp_dir)
+            .join(format!("superbook-pdf-spill-{}", Uuid::new_v4()));
+        let _ = fs::create_dir_all(&spill_root);
+
+        if total == 0 {
+            return SpilledResult {
+                spill_root: SpillGuard(spill_root),
+                errors: vec![],
+                duration: Duration::ZERO,
+                processed_count: 0,
+                total: 0,
+                _marker: PhantomData,
+            };
+        }
+
+        let completed = Arc::new(AtomicUsize::new(0));
+        let progress_callback = self.progress_callback.clone();
+        let errors: Mutex<Vec<(usize, String)>> = Mutex::new(Vec::new());
+
+        let pool = if self.options.num_threads > 0 {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(self.options.num_threads)
+                .build()
+                .ok()
+        } else {
+            None
+        };
+
+        let process_chunk = |chunk: &[(usize, &PathBuf)]| {
+            chunk.par_iter().for_each(|(idx, path)| {
+                let result = processor(path).map_err(|e| e.to_string());
+
+                let done = completed.fetch_add(1, Ordering::Relaxed) + 1;
+                if let Some(ref cb) = progress_callback {
+                    cb(done, total);
+                }
+
+                match result {
+                    Ok(value) => {
+                        let file_path = spill_root.join(format!("{}.json", idx));
+                        let write_result = fs::File::create(&file_path)
+                            .map_err(|e| e.to_string())
+                            .and_then(|f| serde_json::to_writer(f, &value).map_err(|e| e.to_string()));
+                        if let Err(e) = write_result {
+                            errors.lock().unwrap().push((*idx, format!("spill write failed: {}", e)));
+                        }
+                    }
+                    Err(e) => errors.lock().unwrap().push((*idx, e)),
+                }
+            });
+        };
+
+        let indexed_items: Vec<_> = items.iter().enumerate().collect();
+        let chunk_size = if self.options.chunk_size > 0 {
+            self.options.chunk_size
+        } else {
+            total
+        };
+
+        for chunk in indexed_items.chunks(chunk_size) {
+            if let Some(ref pool) = pool {
+                pool.install(|| process_chunk(chunk));
+            } else {
+                process_chunk(chunk);
+            }
+        }
+
+        SpilledResult {
+            spill_root: SpillGuard(spill_root),
+            errors: errors.into_inner().unwrap(),
+            duration: start.elapsed(),
+            processed_count: total,
+            total,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Fold items into a single accumulator instead of collecting a
+    /// `Vec<(usize, T)>`.
+    ///
+    /// Each rayon split computes its own partial accumulator over its slice
+    /// of items (starting from `identity` and folding in each mapped value
+    /// with `combine`), and the partials are then merged pairwise with the
+    /// same `combine`, so peak memory is O(threads) rather than O(pages).
+    /// Useful for whole-document statistics such as total OCR character
+    /// count, max page dimensions, or a combined histogram for
+    /// auto-contrast. `continue_on_error` semantics are preserved: a failing
+    /// item is recorded in the returned errors and skipped rather than
+    /// aborting the fold.
+    pub fn reduce<T, E, M, C>(&self, items: &[PathBuf], identity: T, mapper: M, combine: C) -> ReduceResult<T>
+    where
+        M: Fn(&Path) -> Result<T, E> + Sync + Send,
+        C: Fn(T, T) -> T + Sync + Send,
+        E: std::fmt::Display,
+        T: Clone + Send + Sync,
+    {
+        let start = Instant::now();
+        let total = items.len();
+
+        if total == 0 {
+            return ReduceResult {
+                accumulator: identity,
+                errors: vec![],
+                duration: Duration::ZERO,
+                processed_count: 0,
+            };
+        }
+
+        let completed = Arc::new(AtomicUsize::new(0));
+        let progress_callback = self.progress_callback.clone();
+        let errors: Mutex<Vec<(usize, String)>> = Mutex::new(Vec::new());
+
+        let run = || {
+            items
+                .par_iter()
+                .enumerate()
+                .fold(
+                    || identity.clone(),
+                    |acc, (idx, path)| {
+                        let done = completed.fetch_add(1, Ordering::Relaxed) + 1;
+                        if let Some(ref cb) = progress_callback {
+                            cb(done, total);
+                        }
+
+                        match mapper(path) {
+                            Ok(value) => combine(acc, value),
+                            Err(e) => {
+                                errors.lock().unwrap().push((idx, e.to_string()));
+                                acc
+                            }
+                        }
+                    },
+                )
+                .reduce(|| identity.clone(), &combine)
+        };
+
+        let accumulator = if self.options.num_threads > 0 {
+            match rayon::ThreadPoolBuilder::new()
+                .num_threads(self.options.num_threads)
+                .build()
+            {
+                Ok(pool) => pool.install(run),
+                Err(_) => run(),
+            }
+        } else {
+            run()
+        };
+
+        ReduceResult {
+            accumulator,
+            errors: errors.into_inner().unwrap(),
+            duration: start.elapsed(),
+            processed_count: total,
+        }
+    }
+
+    /// Process items in parallel, yielding results in original input order as
+    /// soon as each prefix is ready rather than waiting for the whole batch.
+    ///
+    /// Unlike [`process`](Self::process), which only produces an ordering
+    /// once everything has finished, this lets a downstream consumer (e.g.
+    /// PDF assembly) start on page 0 while later pages are still rendering.
+    /// A reorder buffer holds out-of-order completions until the next index
+    /// the consumer is waiting on becomes available; `ParallelOptions::max_buffered`
+    /// bounds how far workers may run ahead of the consumer, so a slow consumer applies
+    /// back-pressure instead of letting the buffer grow unboundedly.
+    pub fn process_ordered_iter<T, E, F>(&self, items: &[PathBuf], processor: F) -> OrderedResults<T>
+    where
+        F: Fn(&Path) -> Result<T, E> + Sync + Send + 'static,
+        E: std::fmt::Display,
+        T: Send + 'static,
+    {
+        let total = items.len();
+        let shared = Arc::new(ReorderBuffer::new(total));
+
+        if total == 0 {
+            return OrderedResults {
+                shared,
+                yielded: 0,
+                worker: None,
+            };
+        }
+
+        let items_owned: Vec<PathBuf> = items.to_vec();
+        let max_buffered = self.options.max_buffered;
+        let num_threads = self.options.num_threads;
+        let completed = Arc::new(AtomicUsize::new(0));
+        let progress_callback = self.progress_callback.clone();
+
+        let worker_shared = Arc::clone(&shared);
+        let worker = std::thread::spawn(move || {
+            let run = || {
+                let _ = items_owned.par_iter().enumerate().try_for_each(|(idx, path)| {
+                    if !worker_shared.acquire_slot(max_buffered) {
+                        return Err(());
+                    }
+
+                    let result = processor(path).map_err(|e| e.to_string());
+
+                    let done = completed.fetch_add(1, Ordering::Relaxed) + 1;
+                    if let Some(ref cb) = progress_callback {
+                        cb(done, total);
+                    }
+
+                    worker_shared.publish(idx, result);
+                    Ok(())
+                });
+            };
+
+            if num_threads > 0 {
+                if let Ok(pool) = rayon::ThreadPoolBuilder::new()
+                    .num_threads(num_threads)
+                    .build()
+                {
+                    pool.install(run);
+                    return;
+                }
+            }
+
+            run();
+        });
+
+        OrderedResults {
+            shared,
+            yielded: 0,
+            worker: Some(worker),
+        }
+    }
+
+    /// Process items with a simple function (no error handling)
+    pub fn map<T, F>(&self, items: &[PathBuf], mapper: F) -> Vec<T>
+    where
+        F: Fn(&Path) -> T + Sync + Send,
+        T: Send,
+    {
+        if self.options.num_threads > 0 {
+            if let Ok(pool) = rayon::ThreadPoolBuilder::new()
+                .num_threads(self.options.num_threads)
+                .build()
+            {
+                return pool.install(|| items.par_iter().map(|p| mapper(p)).collect());
+            }
+        }
+
+        items.par_iter().map(|p| mapper(p)).collect()
+    }
+}
+
+impl Default for ParallelProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Convenience function for parallel processing
+pub fn parallel_process<T, E, F>(
+    inputs: &[PathBuf],
+    processor: F,
+    options: &ParallelOptions,
+) -> ParallelResult<T>
+where
+    F: Fn(&Path) -> Result<T, E> + Sync + Send,
+    E: std::fmt::Display,
+    T: Send,
+{
+    ParallelProcessor::with_options(options.clone()).process(inputs, processor)
+}
+
+/// Convenience function for streaming, in-order parallel processing
+pub fn parallel_process_ordered_iter<T, E, F>(
+    inputs: &[PathBuf],
+    processor: F,
+    options: &ParallelOptions,
+) -> OrderedResults<T>
+where
+    F: Fn(&Path) -> Result<T, E> + Sync + Send + 'static,
+    E: std::fmt::Display,
+    T: Send + 'static,
+{
+    ParallelProcessor::with_options(options.clone()).process_ordered_iter(inputs, processor)
+}
+
+/// Convenience function for disk-spilled parallel processing
+pub fn parallel_process_spilled<T, E, F>(
+    inputs: &[PathBuf],
+    processor: F,
+    options: &ParallelOptions,
+) -> SpilledResult<T>
+where
+    F: Fn(&Path) -> Result<T, E> + Sync + Send,
+    E: std::fmt::Display,
+    T: Send + Serialize,
+{
+    ParallelProcessor::with_options(options.clone()).process_spilled(inputs, processor)
+}
+
+/// Convenience function for parallel reduce
+pub fn parallel_reduce<T, E, M, C>(
+    inputs: &[PathBuf],
+    identity: T,
+    mapper: M,
+    combine: C,
+    options: &ParallelOptions,
+) -> ReduceResult<T>
+where
+    M: Fn(&Path) -> Result<T, E> + Sync + Send,
+    C: Fn(T, T) -> T + Sync + Send,
+    E: std::fmt::Display,
+    T: Clone + Send + Sync,
+{
+    ParallelProcessor::with_options(options.clone()).reduce(inputs, identity, mapper, combine)
+}
+
+/// Parallel map with simple function
+pub fn parallel_map<T, F>(inputs: &[PathBuf], mapper: F, num_threads: usize) -> Vec<T>
+where
+    F: Fn(&Path) -> T + Sync + Send,
+    T: Send,
+{
+    ParallelProcessor::with_options(ParallelOptions::with_threads(num_threads)).map(inputs, mapper)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    // ============ TC PAR-001: Basic parallel processing ============
+
+    #[test]
+    fn test_par001_parallel_process_basic() {
+        let dir = tempdir().unwrap();
+        let paths: Vec<PathBuf> = (0..10)
             .map(|i| {
                 let path = dir.path().join(format!("file_{}.txt", i));
                 let mut f = File::create(&path).unwrap();
@@ -496,6 +1524,8 @@ mod tests {
             errors: vec![(2, "error".to_string())],
             duration: Duration::ZERO,
             processed_count: 3,
+            cancelled: false,
+            skipped_count: 0,
         };
 
         let rate = result.success_rate();
@@ -515,6 +1545,9 @@ mod tests {
 
         let err3 = ParallelError::AllTasksFailed(10);
         assert!(err3.to_string().contains("10 tasks"));
+
+        let err4 = ParallelError::Cancelled;
+        assert!(err4.to_string().contains("cancelled"));
     }
 
     #[test]
@@ -662,6 +1695,7 @@ mod tests {
             num_threads: 4,
             chunk_size: 3,
             continue_on_error: true,
+            ..Default::default()
         };
 
         let processor = ParallelProcessor::with_options(options);
@@ -704,6 +1738,455 @@ mod tests {
         assert_eq!(result.processed_count, 1);
     }
 
+    // ============ Subprocess output capture tests ============
+
+    #[test]
+    fn test_command_output_captures_stdout_lines() {
+        let mut lines = Vec::new();
+        let mut command = std::process::Command::new("printf");
+        command.arg("line1\nline2\nline3");
+
+        let output = CommandOutput::run(&mut command, |line| lines.push(line.to_string())).unwrap();
+
+        assert_eq!(output.stdout, "line1\nline2\nline3");
+        assert_eq!(lines, vec!["line1", "line2", "line3"]);
+        assert_eq!(output.status, Some(0));
+    }
+
+    #[test]
+    fn test_command_output_captures_stderr() {
+        let mut lines = Vec::new();
+        let mut command = std::process::Command::new("sh");
+        command.args(["-c", "echo oops 1>&2"]);
+
+        let output = CommandOutput::run(&mut command, |line| lines.push(line.to_string())).unwrap();
+
+        assert_eq!(output.stderr.trim_end(), "oops");
+        assert!(lines.iter().any(|l| l == "oops"));
+    }
+
+    #[test]
+    fn test_line_drain_reassembles_multi_byte_char_split_across_pushes() {
+        let mut drain = LineDrain::new();
+        let line = "caf\u{e9}\n".as_bytes().to_vec(); // "café\n"
+        let split_at = line.len() - 2; // splits the 2-byte 'é' encoding in half
+
+        drain.push(&line[..split_at]);
+        drain.push(&line[split_at..]);
+
+        let mut lines = Vec::new();
+        drain.flush_complete_lines(|l| lines.push(l.to_string()));
+        assert_eq!(lines, vec!["caf\u{e9}"]);
+    }
+
+    #[test]
+    fn test_process_with_logging_tags_lines_by_index() {
+        let dir = tempdir().unwrap();
+        let paths: Vec<PathBuf> = (0..3)
+            .map(|i| {
+                let path = dir.path().join(format!("{}.txt", i));
+                File::create(&path).unwrap();
+                path
+            })
+            .collect();
+
+        let logged: Arc<Mutex<Vec<(usize, String)>>> = Arc::new(Mutex::new(Vec::new()));
+        let logged_clone = Arc::clone(&logged);
+
+        let processor = ParallelProcessor::new().with_log_callback(move |idx, line| {
+            logged_clone.lock().unwrap().push((idx, line.to_string()));
+        });
+
+        let result = processor.process_with_logging(&paths, |path, log| {
+            log(&format!("processing {}", path.display()));
+            Ok::<_, String>(true)
+        });
+
+        assert!(result.is_success());
+        let logged = logged.lock().unwrap();
+        assert_eq!(logged.len(), 3);
+        for (idx, line) in logged.iter() {
+            assert!(line.contains(&paths[*idx].display().to_string()));
+        }
+    }
+
+    // ============ FD limit tests ============
+
+    #[test]
+    fn test_raise_fd_limit_does_not_panic() {
+        // Exercises the real getrlimit/setrlimit path (or the Windows no-op).
+        let _ = raise_fd_limit();
+    }
+
+    #[test]
+    fn test_raise_fd_limit_enabled_by_default() {
+        let options = ParallelOptions::default();
+        assert!(options.raise_fd_limit);
+    }
+
+    #[test]
+    fn test_process_with_fd_limit_disabled() {
+        let dir = tempdir().unwrap();
+        let paths: Vec<PathBuf> = (0..5)
+            .map(|i| {
+                let path = dir.path().join(format!("{}.txt", i));
+                File::create(&path).unwrap();
+                path
+            })
+            .collect();
+
+        let options = ParallelOptions {
+            raise_fd_limit: false,
+            ..Default::default()
+        };
+        let processor = ParallelProcessor::with_options(options);
+        let result = processor.process(&paths, |path| Ok::<_, String>(path.exists()));
+
+        assert!(result.is_success());
+        assert_eq!(result.results.len(), 5);
+    }
+
+    // ============ Cancellation tests ============
+
+    #[test]
+    fn test_interrupt_flag_skips_remaining_items() {
+        use std::sync::atomic::AtomicBool;
+
+        let dir = tempdir().unwrap();
+        let paths: Vec<PathBuf> = (0..20)
+            .map(|i| {
+                let path = dir.path().join(format!("{}.txt", i));
+                File::create(&path).unwrap();
+                path
+            })
+            .collect();
+
+        let flag = Arc::new(AtomicBool::new(true));
+        let options = ParallelOptions::with_interrupt(Arc::clone(&flag));
+        let processor = ParallelProcessor::with_options(options);
+
+        let result = processor.process(&paths, |path| Ok::<_, String>(path.exists()));
+
+        assert!(result.cancelled);
+        assert_eq!(result.skipped_count, 20);
+        assert_eq!(result.results.len(), 0);
+        assert_eq!(result.processed_count, 20);
+    }
+
+    #[test]
+    fn test_interrupt_flag_not_set_runs_to_completion() {
+        let dir = tempdir().unwrap();
+        let paths: Vec<PathBuf> = (0..10)
+            .map(|i| {
+                let path = dir.path().join(format!("{}.txt", i));
+                File::create(&path).unwrap();
+                path
+            })
+            .collect();
+
+        let options = ParallelOptions::default();
+        let processor = ParallelProcessor::with_options(options);
+        let result = processor.process(&paths, |path| Ok::<_, String>(path.exists()));
+
+        assert!(!result.cancelled);
+        assert_eq!(result.skipped_count, 0);
+        assert_eq!(result.results.len(), 10);
+    }
+
+    #[test]
+    fn test_default_options_has_no_interrupt_flag() {
+        let options = ParallelOptions::default();
+        assert!(options.should_interrupt.is_none());
+    }
+
+    // ============ Disk-spill tests ============
+
+    #[test]
+    fn test_spilled_results_round_trip_in_order() {
+        let dir = tempdir().unwrap();
+        let spill_dir = tempdir().unwrap();
+        let paths: Vec<PathBuf> = (0..20)
+            .map(|i| {
+                let path = dir.path().join(format!("{:02}.txt", i));
+                File::create(&path).unwrap();
+                path
+            })
+            .collect();
+
+        let options = ParallelOptions::with_spill(Some(spill_dir.path().to_path_buf()));
+        let processor = ParallelProcessor::with_options(options);
+        let spilled = processor.process_spilled(&paths, |path| {
+            let name = path.file_stem().unwrap().to_str().unwrap();
+            Ok::<_, String>(name.parse::<usize>().unwrap())
+        });
+
+        assert!(spilled.is_success());
+        assert_eq!(spilled.processed_count, 20);
+
+        let collected: Vec<usize> = spilled
+            .ordered_results()
+            .map(|r| r.unwrap())
+            .collect();
+        assert_eq!(collected, (0..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_spilled_results_cleans_up_directory() {
+        let dir = tempdir().unwrap();
+        let spill_dir = tempdir().unwrap();
+        let paths: Vec<PathBuf> = (0..5)
+            .map(|i| {
+                let path = dir.path().join(format!("{}.txt", i));
+                File::create(&path).unwrap();
+                path
+            })
+            .collect();
+
+        let options = ParallelOptions::with_spill(Some(spill_dir.path().to_path_buf()));
+        let processor = ParallelProcessor::with_options(options);
+        let spilled = processor.process_spilled(&paths, |path| Ok::<_, String>(path.exists()));
+
+        let run_dirs_before: Vec<_> = fs::read_dir(spill_dir.path()).unwrap().collect();
+        assert_eq!(run_dirs_before.len(), 1);
+
+        let iter = spilled.ordered_results();
+        drop(iter);
+
+        let run_dirs_after: Vec<_> = fs::read_dir(spill_dir.path()).unwrap().collect();
+        assert_eq!(run_dirs_after.len(), 0);
+    }
+
+    #[test]
+    fn test_spilled_result_cleans_up_directory_without_ordered_results() {
+        let dir = tempdir().unwrap();
+        let spill_dir = tempdir().unwrap();
+        let paths: Vec<PathBuf> = (0..5)
+            .map(|i| {
+                let path = dir.path().join(format!("{}.txt", i));
+                File::create(&path).unwrap();
+                path
+            })
+            .collect();
+
+        let options = ParallelOptions::with_spill(Some(spill_dir.path().to_path_buf()));
+        let processor = ParallelProcessor::with_options(options);
+        let spilled = processor.process_spilled(&paths, |path| Ok::<_, String>(path.exists()));
+
+        let run_dirs_before: Vec<_> = fs::read_dir(spill_dir.path()).unwrap().collect();
+        assert_eq!(run_dirs_before.len(), 1);
+
+        // Never call .ordered_results() here; dropping SpilledResult itself
+        // should still remove the spill directory.
+        drop(spilled);
+
+        let run_dirs_after: Vec<_> = fs::read_dir(spill_dir.path()).unwrap().collect();
+        assert_eq!(run_dirs_after.len(), 0);
+    }
+
+    #[test]
+    fn test_spilled_results_reports_errors_and_skips_them_on_stream() {
+        let dir = tempdir().unwrap();
+        let spill_dir = tempdir().unwrap();
+        let mut paths = Vec::new();
+        for i in 0..3 {
+            let path = dir.path().join(format!("valid_{}.txt", i));
+            File::create(&path).unwrap();
+            paths.push(path);
+        }
+        paths.push(PathBuf::from("/nonexistent/missing.txt"));
+
+        let options = ParallelOptions::with_spill(Some(spill_dir.path().to_path_buf()));
+        let processor = ParallelProcessor::with_options(options);
+        let spilled = processor.process_spilled(&paths, |path| {
+            if path.exists() {
+                Ok(true)
+            } else {
+                Err("missing")
+            }
+        });
+
+        assert_eq!(spilled.errors.len(), 1);
+        let collected: Vec<bool> = spilled.ordered_results().map(|r| r.unwrap()).collect();
+        assert_eq!(collected, vec![true, true, true]);
+    }
+
+    // ============ Reduce/fold tests ============
+
+    #[test]
+    fn test_reduce_sums_values() {
+        let dir = tempdir().unwrap();
+        let paths: Vec<PathBuf> = (1..=10)
+            .map(|i| {
+                let path = dir.path().join(format!("{}.txt", i));
+                let mut f = File::create(&path).unwrap();
+                write!(f, "{}", "x".repeat(i)).unwrap();
+                path
+            })
+            .collect();
+
+        let options = ParallelOptions::default();
+        let processor = ParallelProcessor::with_options(options);
+        let result = processor.reduce(
+            &paths,
+            0usize,
+            |path| Ok::<_, String>(std::fs::metadata(path).unwrap().len() as usize),
+            |acc, len| acc + len,
+        );
+
+        assert!(result.is_success());
+        assert_eq!(result.accumulator, (1..=10).sum::<usize>());
+        assert_eq!(result.processed_count, 10);
+    }
+
+    #[test]
+    fn test_reduce_tracks_errors() {
+        let paths: Vec<PathBuf> = (0..5)
+            .map(|i| PathBuf::from(format!("/nonexistent/path_{}", i)))
+            .collect();
+
+        let options = ParallelOptions::default();
+        let processor = ParallelProcessor::with_options(options);
+        let result = processor.reduce(
+            &paths,
+            0usize,
+            |path| {
+                if path.exists() {
+                    Ok(1usize)
+                } else {
+                    Err("missing")
+                }
+            },
+            |acc, v| acc + v,
+        );
+
+        assert!(!result.is_success());
+        assert_eq!(result.errors.len(), 5);
+        assert_eq!(result.accumulator, 0);
+    }
+
+    #[test]
+    fn test_reduce_empty_input() {
+        let paths: Vec<PathBuf> = vec![];
+        let options = ParallelOptions::default();
+        let processor = ParallelProcessor::with_options(options);
+        let result = processor.reduce(&paths, 42usize, |_| Ok::<_, String>(1), |acc, v| acc + v);
+
+        assert_eq!(result.accumulator, 42);
+        assert_eq!(result.processed_count, 0);
+    }
+
+    #[test]
+    fn test_reduce_max_dimensions() {
+        let dir = tempdir().unwrap();
+        let sizes = [(100u32, 200u32), (300, 50), (20, 400)];
+        let paths: Vec<PathBuf> = sizes
+            .iter()
+            .enumerate()
+            .map(|(i, (w, h))| {
+                let path = dir.path().join(format!("{}_{}x{}.txt", i, w, h));
+                File::create(&path).unwrap();
+                path
+            })
+            .collect();
+
+        let options = ParallelOptions::default();
+        let processor = ParallelProcessor::with_options(options);
+        let result = processor.reduce(
+            &paths,
+            (0u32, 0u32),
+            |path| {
+                let stem = path.file_stem().unwrap().to_str().unwrap();
+                let dims = stem.split('_').nth(1).unwrap();
+                let mut parts = dims.split('x');
+                let w: u32 = parts.next().unwrap().parse().unwrap();
+                let h: u32 = parts.next().unwrap().parse().unwrap();
+                Ok::<_, String>((w, h))
+            },
+            |acc, (w, h)| (acc.0.max(w), acc.1.max(h)),
+        );
+
+        assert_eq!(result.accumulator, (300, 400));
+    }
+
+    // ============ Ordered iterator tests ============
+
+    #[test]
+    fn test_ordered_iter_yields_in_order() {
+        let dir = tempdir().unwrap();
+        let paths: Vec<PathBuf> = (0..20)
+            .map(|i| {
+                let path = dir.path().join(format!("{:02}.txt", i));
+                File::create(&path).unwrap();
+                path
+            })
+            .collect();
+
+        let options = ParallelOptions::default();
+        let processor = ParallelProcessor::with_options(options);
+        let iter = processor.process_ordered_iter(&paths, |path| {
+            let name = path.file_stem().unwrap().to_str().unwrap();
+            Ok::<_, String>(name.parse::<usize>().unwrap())
+        });
+
+        let collected: Vec<usize> = iter.map(|r| r.unwrap()).collect();
+        assert_eq!(collected, (0..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_ordered_iter_empty_input() {
+        let paths: Vec<PathBuf> = vec![];
+        let options = ParallelOptions::default();
+        let processor = ParallelProcessor::with_options(options);
+        let iter = processor.process_ordered_iter(&paths, |_| Ok::<_, String>(true));
+
+        assert_eq!(iter.collect::<Vec<_>>().len(), 0);
+    }
+
+    #[test]
+    fn test_ordered_iter_respects_max_buffered() {
+        let dir = tempdir().unwrap();
+        let paths: Vec<PathBuf> = (0..10)
+            .map(|i| {
+                let path = dir.path().join(format!("{:02}.txt", i));
+                File::create(&path).unwrap();
+                path
+            })
+            .collect();
+
+        let options = ParallelOptions::with_max_buffered(2);
+        let processor = ParallelProcessor::with_options(options);
+        let iter = processor.process_ordered_iter(&paths, |path| {
+            let name = path.file_stem().unwrap().to_str().unwrap();
+            Ok::<_, String>(name.parse::<usize>().unwrap())
+        });
+
+        // A tiny window should still deliver every item, in order, just more slowly.
+        let collected: Vec<usize> = iter.map(|r| r.unwrap()).collect();
+        assert_eq!(collected, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_ordered_iter_partial_consumption_does_not_hang() {
+        let dir = tempdir().unwrap();
+        let paths: Vec<PathBuf> = (0..50)
+            .map(|i| {
+                let path = dir.path().join(format!("{:02}.txt", i));
+                File::create(&path).unwrap();
+                path
+            })
+            .collect();
+
+        let options = ParallelOptions::with_max_buffered(3);
+        let processor = ParallelProcessor::with_options(options);
+        let mut iter = processor.process_ordered_iter(&paths, |path| Ok::<_, String>(path.exists()));
+
+        // Only consume a few, then drop the iterator; the worker thread must
+        // be unblocked and joined rather than left running forever.
+        assert!(iter.next().is_some());
+        assert!(iter.next().is_some());
+        drop(iter);
+    }
+
     #[test]
     fn test_parallel_map_preserves_order() {
         let paths: Vec<PathBuf> = (0..20).map(|i| PathBuf::from(format!("{:02}", i))).collect();
@@ -0,0 +1,374 @@
+//! Batch job queue for grouped PDF conversions
+//!
+//! A `BatchJob` groups a set of individual [`Job`]s (see [`super::job`]) so
+//! callers can track and cancel them together. The batch queue itself is
+//! async since it is driven from the web server's request handlers.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::dump::{QueueDump, RestoreSummary};
+use super::job::{ConvertOptions, Job, JobQueue, JobStatus};
+use super::persistence::{JobStore, StoreError};
+
+/// Relative priority of a batch within the queue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+pub enum Priority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl Priority {
+    /// Numeric weight used when ordering batches, higher runs first
+    pub fn value(&self) -> u8 {
+        match self {
+            Priority::Low => 0,
+            Priority::Normal => 1,
+            Priority::High => 2,
+        }
+    }
+}
+
+/// Lifecycle status of a batch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BatchStatus {
+    Queued,
+    Processing,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+/// A group of related conversion jobs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchJob {
+    pub id: Uuid,
+    pub options: ConvertOptions,
+    pub priority: Priority,
+    pub status: BatchStatus,
+    pub job_ids: Vec<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl BatchJob {
+    /// Create a new, empty batch in the `Queued` state
+    pub fn new(options: ConvertOptions, priority: Priority) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            options,
+            priority,
+            status: BatchStatus::Queued,
+            job_ids: Vec::new(),
+            created_at: Utc::now(),
+            started_at: None,
+            completed_at: None,
+        }
+    }
+
+    /// Number of jobs attached to this batch
+    pub fn job_count(&self) -> usize {
+        self.job_ids.len()
+    }
+
+    /// Transition the batch to `Processing`
+    pub fn start(&mut self) {
+        self.status = BatchStatus::Processing;
+        self.started_at = Some(Utc::now());
+    }
+
+    /// Transition the batch to `Cancelled`
+    pub fn cancel(&mut self) {
+        self.status = BatchStatus::Cancelled;
+        self.completed_at = Some(Utc::now());
+    }
+}
+
+/// Aggregate progress across every job in a batch
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BatchProgress {
+    pub total: usize,
+    pub completed: usize,
+    pub failed: usize,
+    /// Jobs currently waiting out a retry backoff
+    pub retrying: usize,
+    pub pending: usize,
+}
+
+impl BatchProgress {
+    /// Create a fresh progress snapshot with every job still pending
+    pub fn new(total: usize) -> Self {
+        Self {
+            total,
+            completed: 0,
+            failed: 0,
+            retrying: 0,
+            pending: total,
+        }
+    }
+
+    /// Percentage of jobs that have finished, successfully or not. Jobs that
+    /// are retrying are still in flight and don't count toward this yet.
+    pub fn percent(&self) -> u32 {
+        if self.total == 0 {
+            return 0;
+        }
+        ((self.completed + self.failed) * 100 / self.total) as u32
+    }
+
+    /// Whether every job in the batch has reached a terminal state
+    pub fn is_complete(&self) -> bool {
+        self.completed + self.failed >= self.total
+    }
+}
+
+/// Async queue of [`BatchJob`]s, optionally backed by persistent storage
+#[derive(Clone)]
+pub struct BatchQueue {
+    job_queue: JobQueue,
+    batches: Arc<RwLock<HashMap<Uuid, BatchJob>>>,
+    store: Option<Arc<dyn JobStore>>,
+}
+
+impl BatchQueue {
+    /// Create an empty, non-persistent batch queue over `job_queue`
+    pub fn new(job_queue: JobQueue) -> Self {
+        Self {
+            job_queue,
+            batches: Arc::new(RwLock::new(HashMap::new())),
+            store: None,
+        }
+    }
+
+    /// Create a batch queue backed by `store`, loading any batches left over
+    /// from a previous run
+    pub async fn with_store(job_queue: JobQueue, store: Arc<dyn JobStore>) -> Self {
+        let loaded = store.load_batches().unwrap_or_default();
+        let map = loaded.into_iter().map(|batch| (batch.id, batch)).collect();
+        Self {
+            job_queue,
+            batches: Arc::new(RwLock::new(map)),
+            store: Some(store),
+        }
+    }
+
+    /// Create a `Job` for each filename and attach it to the batch, submitting
+    /// each job (at the batch's priority) to the underlying job queue
+    pub fn create_jobs(&self, batch: &mut BatchJob, filenames: &[String]) {
+        for filename in filenames {
+            let job = Job::new(filename, batch.options.clone()).with_priority(batch.priority);
+            batch.job_ids.push(job.id);
+            self.job_queue.submit(job);
+        }
+    }
+
+    async fn persist(&self, batch: &BatchJob) {
+        if let Some(store) = &self.store {
+            let _ = store.save_batch(batch);
+        }
+    }
+
+    /// Submit a batch to the queue
+    pub async fn submit(&self, batch: BatchJob) {
+        self.persist(&batch).await;
+        self.batches.write().await.insert(batch.id, batch);
+    }
+
+    /// Fetch a batch by id
+    pub async fn get(&self, id: Uuid) -> Option<BatchJob> {
+        self.batches.read().await.get(&id).cloned()
+    }
+
+    /// Apply `f` to the batch with the given id, persisting the result
+    pub async fn update<F: FnOnce(&mut BatchJob)>(&self, id: Uuid, f: F) -> Option<BatchJob> {
+        let updated = {
+            let mut batches = self.batches.write().await;
+            let batch = batches.get_mut(&id)?;
+            f(batch);
+            batch.clone()
+        };
+        self.persist(&updated).await;
+        Some(updated)
+    }
+
+    /// List all batches currently tracked by the queue
+    pub async fn list(&self) -> Vec<BatchJob> {
+        self.batches.read().await.values().cloned().collect()
+    }
+
+    /// Number of batches that are queued or actively processing
+    pub async fn active_count(&self) -> usize {
+        self.batches
+            .read()
+            .await
+            .values()
+            .filter(|b| matches!(b.status, BatchStatus::Queued | BatchStatus::Processing))
+            .count()
+    }
+
+    /// Compute aggregate progress across all jobs in a batch
+    pub async fn get_progress(&self, id: Uuid) -> Option<BatchProgress> {
+        let batch = self.get(id).await?;
+        let mut progress = BatchProgress::new(batch.job_ids.len());
+        for job_id in &batch.job_ids {
+            if let Some(job) = self.job_queue.get(*job_id) {
+                match job.status {
+                    JobStatus::Completed => progress.completed += 1,
+                    JobStatus::Failed | JobStatus::Cancelled | JobStatus::DeadLettered => {
+                        progress.failed += 1
+                    }
+                    JobStatus::Retrying => progress.retrying += 1,
+                    JobStatus::Queued | JobStatus::Processing => {}
+                }
+            }
+        }
+        progress.pending = progress
+            .total
+            .saturating_sub(progress.completed + progress.failed + progress.retrying);
+        Some(progress)
+    }
+
+    /// Cancel every unfinished job in a batch, returning `(cancelled, completed)`
+    pub async fn cancel(&self, id: Uuid) -> Option<(usize, usize)> {
+        let batch = self.get(id).await?;
+        let mut cancelled = 0;
+        let mut completed = 0;
+
+        for job_id in &batch.job_ids {
+            match self.job_queue.get(*job_id).map(|j| j.status) {
+                Some(JobStatus::Completed) => completed += 1,
+                Some(JobStatus::Cancelled) => cancelled += 1,
+                // Already terminally failed; leave it dead-lettered rather
+                // than overwriting it with a cancellation.
+                Some(JobStatus::DeadLettered) => {}
+                Some(_) => {
+                    self.job_queue.cancel(*job_id);
+                    cancelled += 1;
+                }
+                None => {}
+            }
+        }
+
+        self.update(id, |b| b.cancel()).await;
+        Some((cancelled, completed))
+    }
+
+    /// Snapshot every job and batch to a versioned dump file, for backing up
+    /// a server before an upgrade or moving a workload between hosts. Exposed
+    /// as a REST handler via [`super::routes::dump_queues`].
+    pub async fn dump(&self, path: &Path) -> Result<(), StoreError> {
+        let jobs = self.job_queue.list();
+        let batches = self.list().await;
+        QueueDump::new(jobs, batches).write_to(path)
+    }
+
+    /// Load a dump file written by [`BatchQueue::dump`], submitting its jobs
+    /// and batches into this queue. Dumps from older builds are upgraded to
+    /// the current format automatically; see [`super::dump::compat`]. Exposed
+    /// as a REST handler via [`super::routes::restore_queues`].
+    pub async fn restore(&self, path: &Path) -> Result<RestoreSummary, StoreError> {
+        let dump = QueueDump::read_from(path)?;
+        let jobs_restored = dump.jobs.len();
+        let batches_restored = dump.batches.len();
+
+        for job in dump.jobs {
+            self.job_queue.submit(job);
+        }
+
+        for batch in dump.batches {
+            self.batches.write().await.insert(batch.id, batch.clone());
+            self.persist(&batch).await;
+        }
+
+        Ok(RestoreSummary {
+            jobs_restored,
+            batches_restored,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_priority_ordering() {
+        assert!(Priority::High.value() > Priority::Normal.value());
+        assert!(Priority::Normal.value() > Priority::Low.value());
+        assert_eq!(Priority::default(), Priority::Normal);
+    }
+
+    #[test]
+    fn test_batch_job_count() {
+        let batch = BatchJob::new(ConvertOptions::default(), Priority::Low);
+        assert_eq!(batch.job_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_batch_queue_empty_progress() {
+        let job_queue = JobQueue::new();
+        let batch_queue = BatchQueue::new(job_queue);
+
+        let batch = BatchJob::new(ConvertOptions::default(), Priority::Normal);
+        let batch_id = batch.id;
+        batch_queue.submit(batch).await;
+
+        let progress = batch_queue.get_progress(batch_id).await.unwrap();
+        assert_eq!(progress.total, 0);
+        assert!(progress.is_complete());
+    }
+
+    #[tokio::test]
+    async fn test_batch_progress_tracks_retrying_jobs() {
+        let job_queue = JobQueue::new();
+        let batch_queue = BatchQueue::new(job_queue.clone());
+
+        let mut batch = BatchJob::new(ConvertOptions::default(), Priority::Normal);
+        batch_queue.create_jobs(&mut batch, &["a.pdf".to_string()]);
+        let job_id = batch.job_ids[0];
+        let batch_id = batch.id;
+        batch_queue.submit(batch).await;
+
+        job_queue.update(job_id, |j| j.fail("transient".to_string()));
+
+        let progress = batch_queue.get_progress(batch_id).await.unwrap();
+        assert_eq!(progress.retrying, 1);
+        assert_eq!(progress.failed, 0);
+        assert_eq!(progress.pending, 0);
+    }
+
+    #[tokio::test]
+    async fn test_dump_and_restore_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let dump_path = dir.path().join("queue.dump");
+
+        let job_queue = JobQueue::new();
+        let batch_queue = BatchQueue::new(job_queue.clone());
+
+        let mut batch = BatchJob::new(ConvertOptions::default(), Priority::High);
+        batch_queue.create_jobs(&mut batch, &["a.pdf".to_string()]);
+        let batch_id = batch.id;
+        let job_id = batch.job_ids[0];
+        batch_queue.submit(batch).await;
+
+        batch_queue.dump(&dump_path).await.unwrap();
+
+        let restored_job_queue = JobQueue::new();
+        let restored_batch_queue = BatchQueue::new(restored_job_queue.clone());
+        let summary = restored_batch_queue.restore(&dump_path).await.unwrap();
+
+        assert_eq!(summary.jobs_restored, 1);
+        assert_eq!(summary.batches_restored, 1);
+        assert!(restored_job_queue.get(job_id).is_some());
+        assert!(restored_batch_queue.get(batch_id).await.is_some());
+    }
+}
@@ -0,0 +1,283 @@
+//! Versioned snapshot/restore for the job and batch queues
+//!
+//! Serializes the entire in-memory state of `JobQueue` + `BatchQueue` — every
+//! [`Job`], [`BatchJob`], their statuses, progress, and options — to a single
+//! dump file that can be reloaded later. Useful for backing up a server
+//! before an upgrade or moving a workload between hosts.
+//!
+//! Dumps are written atomically (serialized to a temp path, then renamed
+//! over the destination) and carry a [`DumpMeta`] header identifying the
+//! format version, so a dump produced by an older build can still be loaded
+//! after `Job`/`ConvertOptions` gain fields — see [`compat`] for the
+//! version-by-version upgrade path.
+
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::batch::BatchJob;
+use super::job::Job;
+use super::persistence::StoreError;
+
+/// Current on-disk dump format version
+pub const DUMP_VERSION: u32 = 2;
+
+/// Header written at the top of every dump file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DumpMeta {
+    pub version: u32,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Full snapshot of job and batch queue state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueDump {
+    pub meta: DumpMeta,
+    pub jobs: Vec<Job>,
+    pub batches: Vec<BatchJob>,
+}
+
+impl QueueDump {
+    /// Build a dump of the current format version from in-memory state
+    pub fn new(jobs: Vec<Job>, batches: Vec<BatchJob>) -> Self {
+        Self {
+            meta: DumpMeta {
+                version: DUMP_VERSION,
+                created_at: Utc::now(),
+            },
+            jobs,
+            batches,
+        }
+    }
+
+    /// Write the dump to `path` atomically: serialize to a temp file
+    /// alongside it, then rename over the destination
+    pub fn write_to(&self, path: &Path) -> Result<(), StoreError> {
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_vec_pretty(self)?)?;
+        fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    /// Load a dump file, upgrading older format versions to the current one
+    pub fn read_from(path: &Path) -> Result<Self, StoreError> {
+        compat::load(&fs::read(path)?)
+    }
+}
+
+/// Result of restoring a [`QueueDump`] into a running queue
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RestoreSummary {
+    pub jobs_restored: usize,
+    pub batches_restored: usize,
+}
+
+/// Deserializers for older dump formats, upgrading their records into the
+/// current [`Job`]/[`BatchJob`] shapes on import
+pub mod compat {
+    use super::*;
+    use crate::web::job::{ConvertOptions, JobStatus, Progress};
+    use std::path::PathBuf;
+    use uuid::Uuid;
+
+    /// Dump format produced before retry/backoff and cancellation tokens
+    /// were added to `Job` (pre dnobori/DN_SuperBook_PDF_Converter#chunk2-2)
+    pub mod v1 {
+        use super::*;
+
+        #[derive(Debug, Clone, Serialize, Deserialize)]
+        pub struct ConvertOptionsV1 {
+            pub dpi: u32,
+            pub deskew: bool,
+            pub upscale: bool,
+            pub ocr: bool,
+            pub advanced: bool,
+        }
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+        pub enum JobStatusV1 {
+            Queued,
+            Processing,
+            Completed,
+            Failed,
+            Cancelled,
+        }
+
+        #[derive(Debug, Clone, Serialize, Deserialize)]
+        pub struct JobV1 {
+            pub id: Uuid,
+            pub input_filename: String,
+            pub options: ConvertOptionsV1,
+            pub status: JobStatusV1,
+            pub progress: Option<Progress>,
+            pub output_path: Option<PathBuf>,
+            pub error: Option<String>,
+            pub created_at: DateTime<Utc>,
+            pub started_at: Option<DateTime<Utc>>,
+            pub completed_at: Option<DateTime<Utc>>,
+        }
+
+        impl From<JobV1> for Job {
+            fn from(old: JobV1) -> Self {
+                let options = ConvertOptions {
+                    dpi: old.options.dpi,
+                    deskew: old.options.deskew,
+                    upscale: old.options.upscale,
+                    ocr: old.options.ocr,
+                    advanced: old.options.advanced,
+                    ..ConvertOptions::default()
+                };
+                let status = match old.status {
+                    JobStatusV1::Queued => JobStatus::Queued,
+                    JobStatusV1::Processing => JobStatus::Processing,
+                    JobStatusV1::Completed => JobStatus::Completed,
+                    JobStatusV1::Failed => JobStatus::Failed,
+                    JobStatusV1::Cancelled => JobStatus::Cancelled,
+                };
+                let max_attempts = options.max_attempts;
+                Job {
+                    id: old.id,
+                    input_filename: old.input_filename,
+                    options,
+                    // v1 predates per-job priority; restored jobs get the
+                    // `Priority::Normal` default that `Job::new` also uses
+                    priority: Default::default(),
+                    status,
+                    progress: old.progress,
+                    output_path: old.output_path,
+                    error: old.error,
+                    attempts: 0,
+                    max_attempts,
+                    next_attempt_at: None,
+                    created_at: old.created_at,
+                    started_at: old.started_at,
+                    completed_at: old.completed_at,
+                    cancel_token: Default::default(),
+                }
+            }
+        }
+
+        /// `BatchJob` is unchanged since v1 — nothing to upgrade
+        pub type BatchJobV1 = BatchJob;
+
+        #[derive(Debug, Clone, Serialize, Deserialize)]
+        pub struct DumpV1 {
+            pub meta: DumpMeta,
+            pub jobs: Vec<JobV1>,
+            pub batches: Vec<BatchJobV1>,
+        }
+
+        impl From<DumpV1> for QueueDump {
+            fn from(old: DumpV1) -> Self {
+                QueueDump {
+                    meta: DumpMeta {
+                        version: DUMP_VERSION,
+                        created_at: old.meta.created_at,
+                    },
+                    jobs: old.jobs.into_iter().map(Into::into).collect(),
+                    batches: old.batches,
+                }
+            }
+        }
+    }
+
+    /// Current dump format (dnobori/DN_SuperBook_PDF_Converter#chunk2-4 and later)
+    pub mod v2 {
+        pub use super::super::QueueDump as DumpV2;
+    }
+
+    /// Peek at `meta.version` in raw dump bytes without committing to a
+    /// format, then deserialize and upgrade against the matching version
+    pub fn load(data: &[u8]) -> Result<QueueDump, StoreError> {
+        let probe: serde_json::Value = serde_json::from_slice(data)?;
+        let version = probe
+            .get("meta")
+            .and_then(|m| m.get("version"))
+            .and_then(|v| v.as_u64())
+            .unwrap_or(1) as u32;
+
+        match version {
+            1 => Ok(serde_json::from_slice::<v1::DumpV1>(data)?.into()),
+            2 => Ok(serde_json::from_slice::<v2::DumpV2>(data)?),
+            other => Err(StoreError::UnsupportedVersion(other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::web::batch::Priority;
+    use crate::web::job::{ConvertOptions, JobStatus};
+    use tempfile::tempdir;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_dump_round_trip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("queue.dump");
+
+        let job = Job::new("test.pdf", ConvertOptions::default());
+        let job_id = job.id;
+        let batch = BatchJob::new(ConvertOptions::default(), Priority::Normal);
+        let batch_id = batch.id;
+
+        let dump = QueueDump::new(vec![job], vec![batch]);
+        dump.write_to(&path).unwrap();
+
+        let loaded = QueueDump::read_from(&path).unwrap();
+        assert_eq!(loaded.meta.version, DUMP_VERSION);
+        assert_eq!(loaded.jobs.len(), 1);
+        assert_eq!(loaded.jobs[0].id, job_id);
+        assert_eq!(loaded.batches.len(), 1);
+        assert_eq!(loaded.batches[0].id, batch_id);
+    }
+
+    #[test]
+    fn test_dump_write_is_atomic_rename() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("queue.dump");
+
+        let dump = QueueDump::new(Vec::new(), Vec::new());
+        dump.write_to(&path).unwrap();
+
+        assert!(path.exists());
+        assert!(!path.with_extension("tmp").exists());
+    }
+
+    #[test]
+    fn test_v1_dump_upgrades_on_load() {
+        let v1_json = serde_json::json!({
+            "meta": { "version": 1, "created_at": Utc::now() },
+            "jobs": [{
+                "id": Uuid::new_v4(),
+                "input_filename": "legacy.pdf",
+                "options": { "dpi": 300, "deskew": true, "upscale": true, "ocr": false, "advanced": false },
+                "status": "Completed",
+                "progress": null,
+                "output_path": null,
+                "error": null,
+                "created_at": Utc::now(),
+                "started_at": null,
+                "completed_at": null,
+            }],
+            "batches": [],
+        });
+
+        let dump = compat::load(serde_json::to_vec(&v1_json).unwrap().as_slice()).unwrap();
+        assert_eq!(dump.meta.version, DUMP_VERSION);
+        assert_eq!(dump.jobs.len(), 1);
+        assert_eq!(dump.jobs[0].input_filename, "legacy.pdf");
+        assert_eq!(dump.jobs[0].status, JobStatus::Completed);
+        assert_eq!(dump.jobs[0].max_attempts, ConvertOptions::default().max_attempts);
+    }
+
+    #[test]
+    fn test_unsupported_version_is_rejected() {
+        let data = serde_json::json!({ "meta": { "version": 99, "created_at": Utc::now() } });
+        let result = compat::load(serde_json::to_vec(&data).unwrap().as_slice());
+        assert!(matches!(result, Err(StoreError::UnsupportedVersion(99))));
+    }
+}
@@ -0,0 +1,565 @@
+//! Job queue for PDF conversion jobs
+//!
+//! Tracks jobs in memory behind a lock, optionally writing through to a
+//! [`JobStore`](super::persistence::JobStore) so the queue can be
+//! reconstructed after a restart.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::batch::Priority;
+use super::persistence::JobStore;
+
+/// A cheap, clonable flag used to signal that a running job's conversion
+/// pipeline should stop at its next checkpoint
+#[derive(Debug, Clone)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self(Arc::new(AtomicBool::new(false)))
+    }
+
+    /// Signal that work under this token should stop
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether `cancel()` has been called
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Options controlling how a PDF is converted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConvertOptions {
+    pub dpi: u32,
+    pub deskew: bool,
+    pub upscale: bool,
+    pub ocr: bool,
+    pub advanced: bool,
+    /// Number of attempts (including the first) before a job is dead-lettered
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+    /// Base delay for the first retry; doubles each subsequent attempt
+    #[serde(default = "default_base_retry_delay_secs")]
+    pub base_retry_delay_secs: u64,
+    /// Upper bound on the computed backoff delay
+    #[serde(default = "default_max_retry_delay_secs")]
+    pub max_retry_delay_secs: u64,
+    /// Spread retries out by randomizing the delay up to the backoff cap
+    #[serde(default = "default_retry_jitter")]
+    pub retry_jitter: bool,
+}
+
+fn default_max_attempts() -> u32 {
+    3
+}
+
+fn default_base_retry_delay_secs() -> u64 {
+    2
+}
+
+fn default_max_retry_delay_secs() -> u64 {
+    60
+}
+
+fn default_retry_jitter() -> bool {
+    true
+}
+
+/// Alias matching the `Web`-prefixed naming used by the public API surface
+/// re-exported at the crate root
+pub type WebConvertOptions = ConvertOptions;
+
+impl Default for ConvertOptions {
+    fn default() -> Self {
+        Self {
+            dpi: 300,
+            deskew: true,
+            upscale: true,
+            ocr: false,
+            advanced: false,
+            max_attempts: default_max_attempts(),
+            base_retry_delay_secs: default_base_retry_delay_secs(),
+            max_retry_delay_secs: default_max_retry_delay_secs(),
+            retry_jitter: default_retry_jitter(),
+        }
+    }
+}
+
+/// Lifecycle status of a conversion job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JobStatus {
+    Queued,
+    Processing,
+    /// Failed, but scheduled to be retried once `next_attempt_at` passes
+    Retrying,
+    Completed,
+    Failed,
+    /// Permanently failed after exhausting `max_attempts`
+    DeadLettered,
+    Cancelled,
+}
+
+/// Progress within a running job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Progress {
+    pub current_step: u32,
+    pub total_steps: u32,
+    pub step_name: String,
+    pub percent: u32,
+}
+
+impl Progress {
+    /// Create a progress update, deriving `percent` from the step counts
+    pub fn new(current_step: u32, total_steps: u32, step_name: impl Into<String>) -> Self {
+        let percent = if total_steps == 0 {
+            0
+        } else {
+            current_step * 100 / total_steps
+        };
+        Self {
+            current_step,
+            total_steps,
+            step_name: step_name.into(),
+            percent,
+        }
+    }
+}
+
+/// Alias matching the `Web`-prefixed naming used by the public API surface
+/// re-exported at the crate root
+pub type WebProgress = Progress;
+
+/// A single PDF conversion job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    pub id: Uuid,
+    pub input_filename: String,
+    pub options: ConvertOptions,
+    /// Relative dispatch weight, copied from the owning batch for jobs
+    /// created via [`super::batch::BatchQueue::create_jobs`]
+    #[serde(default)]
+    pub priority: Priority,
+    pub status: JobStatus,
+    pub progress: Option<Progress>,
+    pub output_path: Option<PathBuf>,
+    pub error: Option<String>,
+    /// Number of failed attempts so far; `fail` increments it, `start` does not
+    pub attempts: u32,
+    /// Attempts allowed before the job is dead-lettered, copied from `options`
+    pub max_attempts: u32,
+    /// When a `Retrying` job becomes eligible for pickup again
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Signalled by `cancel()`; checked by the conversion pipeline at step
+    /// boundaries so it can bail out instead of running to completion.
+    /// Not persisted — a job reloaded after a restart gets a fresh token.
+    #[serde(skip)]
+    pub cancel_token: CancellationToken,
+}
+
+impl Job {
+    /// Create a new job in the `Queued` state
+    pub fn new(input_filename: &str, options: ConvertOptions) -> Self {
+        let max_attempts = options.max_attempts;
+        Self {
+            id: Uuid::new_v4(),
+            input_filename: input_filename.to_string(),
+            options,
+            priority: Priority::default(),
+            status: JobStatus::Queued,
+            progress: None,
+            output_path: None,
+            error: None,
+            attempts: 0,
+            max_attempts,
+            next_attempt_at: None,
+            created_at: Utc::now(),
+            started_at: None,
+            completed_at: None,
+            cancel_token: CancellationToken::new(),
+        }
+    }
+
+    /// Override the dispatch priority assigned by [`Job::new`] (defaults to
+    /// [`Priority::Normal`])
+    pub fn with_priority(mut self, priority: Priority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Transition the job to `Processing`
+    pub fn start(&mut self) {
+        self.status = JobStatus::Processing;
+        self.started_at = Some(Utc::now());
+    }
+
+    /// Record a progress update
+    pub fn update_progress(&mut self, progress: Progress) {
+        self.progress = Some(progress);
+    }
+
+    /// Transition the job to `Completed` with the given output path
+    pub fn complete(&mut self, output_path: PathBuf) {
+        self.status = JobStatus::Completed;
+        self.output_path = Some(output_path);
+        self.completed_at = Some(Utc::now());
+    }
+
+    /// Record a failed attempt, scheduling a retry if attempts remain or
+    /// dead-lettering the job once `max_attempts` is exhausted
+    pub fn fail(&mut self, error: String) {
+        self.error = Some(error);
+        self.attempts += 1;
+
+        if self.attempts < self.max_attempts {
+            self.status = JobStatus::Retrying;
+            self.next_attempt_at = Some(Utc::now() + self.backoff_delay());
+        } else {
+            self.status = JobStatus::DeadLettered;
+            self.completed_at = Some(Utc::now());
+        }
+    }
+
+    /// Delay before the next retry, computed as `base * 2^(attempts - 1)`,
+    /// capped at `max_retry_delay_secs` and optionally randomized down from
+    /// that cap ("full jitter") so retries don't all land at once
+    fn backoff_delay(&self) -> chrono::Duration {
+        let exponent = self.attempts.saturating_sub(1).min(32);
+        let scaled = self.options.base_retry_delay_secs.saturating_mul(1u64 << exponent);
+        let capped = scaled.min(self.options.max_retry_delay_secs);
+
+        let secs = if self.options.retry_jitter && capped > 0 {
+            Utc::now().timestamp_subsec_nanos() as u64 % (capped + 1)
+        } else {
+            capped
+        };
+
+        chrono::Duration::seconds(secs as i64)
+    }
+
+    /// Transition the job to `Cancelled`, signalling its cancellation token
+    /// so an in-flight conversion stops at its next checkpoint
+    pub fn cancel(&mut self) {
+        self.status = JobStatus::Cancelled;
+        self.completed_at = Some(Utc::now());
+        self.cancel_token.cancel();
+    }
+}
+
+/// Receives jobs as they are submitted so they can be scheduled onto a
+/// worker slot. Implemented by
+/// [`Scheduler`](super::worker::Scheduler); kept as a trait here (rather
+/// than `JobQueue` holding a `Scheduler` directly) so the queue and the
+/// scheduler that drains it don't depend on each other's concrete types.
+pub trait JobDispatcher: Send + Sync {
+    /// Hand a freshly submitted job to the dispatcher for scheduling
+    fn dispatch(&self, job_id: Uuid, input_path: PathBuf, priority: Priority);
+}
+
+/// In-memory job queue, optionally backed by persistent storage
+#[derive(Clone)]
+pub struct JobQueue {
+    jobs: Arc<Mutex<HashMap<Uuid, Job>>>,
+    store: Option<Arc<dyn JobStore>>,
+    /// Set via [`JobQueue::set_dispatcher`] once a scheduler is stood up;
+    /// `submit` hands every new job to it so there is a single path from
+    /// "job submitted" to "job dispatched"
+    dispatcher: Arc<Mutex<Option<Arc<dyn JobDispatcher>>>>,
+}
+
+impl JobQueue {
+    /// Create an empty, non-persistent queue
+    pub fn new() -> Self {
+        Self {
+            jobs: Arc::new(Mutex::new(HashMap::new())),
+            store: None,
+            dispatcher: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Create a queue backed by `store`, loading any jobs left over from a
+    /// previous run
+    pub fn with_store(store: Arc<dyn JobStore>) -> Self {
+        let jobs = store.load_jobs().unwrap_or_default();
+        let map = jobs.into_iter().map(|job| (job.id, job)).collect();
+        Self {
+            jobs: Arc::new(Mutex::new(map)),
+            store: Some(store),
+            dispatcher: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Attach the scheduler (or other dispatcher) that `submit` should hand
+    /// new jobs to. Set once, after the scheduler is constructed from this
+    /// same queue.
+    pub fn set_dispatcher(&self, dispatcher: Arc<dyn JobDispatcher>) {
+        *self.dispatcher.lock().unwrap() = Some(dispatcher);
+    }
+
+    fn persist(&self, job: &Job) {
+        if let Some(store) = &self.store {
+            let _ = store.save_job(job);
+        }
+    }
+
+    /// Submit a new job to the queue and, once a dispatcher is attached via
+    /// [`set_dispatcher`](Self::set_dispatcher), hand it off for scheduling
+    pub fn submit(&self, job: Job) {
+        self.persist(&job);
+        let dispatcher = self.dispatcher.lock().unwrap().clone();
+        let (id, input_path, priority) = (
+            job.id,
+            PathBuf::from(&job.input_filename),
+            job.priority,
+        );
+        self.jobs.lock().unwrap().insert(id, job);
+        if let Some(dispatcher) = dispatcher {
+            dispatcher.dispatch(id, input_path, priority);
+        }
+    }
+
+    /// Fetch a job by id
+    pub fn get(&self, id: Uuid) -> Option<Job> {
+        self.jobs.lock().unwrap().get(&id).cloned()
+    }
+
+    /// Apply `f` to the job with the given id, persisting the result
+    pub fn update<F: FnOnce(&mut Job)>(&self, id: Uuid, f: F) -> Option<Job> {
+        let updated = {
+            let mut jobs = self.jobs.lock().unwrap();
+            let job = jobs.get_mut(&id)?;
+            f(job);
+            job.clone()
+        };
+        self.persist(&updated);
+        Some(updated)
+    }
+
+    /// Cancel a job, unless it has already reached a terminal state. Mirrors
+    /// [`BatchQueue::cancel`](super::batch::BatchQueue::cancel)'s guard, so a
+    /// stale client re-requesting cancellation can't stomp the status or
+    /// completion timestamp of a job that already completed, was cancelled,
+    /// or was dead-lettered.
+    pub fn cancel(&self, id: Uuid) -> Option<Job> {
+        let job = self.get(id)?;
+        if matches!(
+            job.status,
+            JobStatus::Completed | JobStatus::Cancelled | JobStatus::DeadLettered
+        ) {
+            return Some(job);
+        }
+        self.update(id, |job| job.cancel())
+    }
+
+    /// List all jobs currently tracked by the queue
+    pub fn list(&self) -> Vec<Job> {
+        self.jobs.lock().unwrap().values().cloned().collect()
+    }
+
+    /// Move every `Retrying` job whose `next_attempt_at` has passed back to
+    /// `Queued`, making it eligible for pickup again
+    pub fn promote_ready_retries(&self) -> Vec<Job> {
+        let now = Utc::now();
+        let due: Vec<Uuid> = self
+            .jobs
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|job| job.status == JobStatus::Retrying)
+            .filter(|job| job.next_attempt_at.is_some_and(|at| at <= now))
+            .map(|job| job.id)
+            .collect();
+
+        due.into_iter()
+            .filter_map(|id| {
+                self.update(id, |job| {
+                    job.status = JobStatus::Queued;
+                    job.next_attempt_at = None;
+                })
+            })
+            .collect()
+    }
+
+    /// Remove a job from the queue and, if persistent, from storage
+    pub fn remove(&self, id: Uuid) -> Option<Job> {
+        let removed = self.jobs.lock().unwrap().remove(&id);
+        if removed.is_some() {
+            if let Some(store) = &self.store {
+                let _ = store.delete_job(id);
+            }
+        }
+        removed
+    }
+}
+
+impl Default for JobQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_percent_rounding() {
+        let progress = Progress::new(5, 12, "Processing images");
+        assert_eq!(progress.percent, 41);
+    }
+
+    #[test]
+    fn test_progress_zero_total_steps() {
+        let progress = Progress::new(0, 0, "Starting");
+        assert_eq!(progress.percent, 0);
+    }
+
+    #[test]
+    fn test_job_lifecycle() {
+        let mut job = Job::new("test.pdf", ConvertOptions::default());
+        assert_eq!(job.status, JobStatus::Queued);
+
+        job.start();
+        assert_eq!(job.status, JobStatus::Processing);
+        assert!(job.started_at.is_some());
+
+        job.complete(PathBuf::from("/out/test.pdf"));
+        assert_eq!(job.status, JobStatus::Completed);
+        assert!(job.completed_at.is_some());
+    }
+
+    #[test]
+    fn test_cancel_signals_token() {
+        let mut job = Job::new("test.pdf", ConvertOptions::default());
+        let token = job.cancel_token.clone();
+        assert!(!token.is_cancelled());
+
+        job.cancel();
+        assert_eq!(job.status, JobStatus::Cancelled);
+        assert!(token.is_cancelled());
+    }
+
+    #[test]
+    fn test_queue_cancel_signals_the_same_token_held_by_a_worker() {
+        let queue = JobQueue::new();
+        let job = Job::new("test.pdf", ConvertOptions::default());
+        let job_id = job.id;
+        let token = job.cancel_token.clone();
+
+        queue.submit(job);
+        assert!(!token.is_cancelled());
+
+        queue.cancel(job_id);
+        assert!(token.is_cancelled());
+    }
+
+    #[test]
+    fn test_queue_cancel_is_a_noop_on_a_completed_job() {
+        let queue = JobQueue::new();
+        let job = Job::new("test.pdf", ConvertOptions::default());
+        let job_id = job.id;
+
+        queue.submit(job);
+        let completed_at = queue
+            .update(job_id, |job| job.complete(PathBuf::from("/out/test.pdf")))
+            .unwrap()
+            .completed_at;
+
+        let result = queue.cancel(job_id).unwrap();
+        assert_eq!(result.status, JobStatus::Completed);
+        assert_eq!(result.completed_at, completed_at);
+    }
+
+    #[test]
+    fn test_job_queue_remove() {
+        let queue = JobQueue::new();
+        let job = Job::new("test.pdf", ConvertOptions::default());
+        let job_id = job.id;
+
+        queue.submit(job);
+        assert!(queue.get(job_id).is_some());
+
+        let removed = queue.remove(job_id);
+        assert!(removed.is_some());
+        assert!(queue.get(job_id).is_none());
+    }
+
+    #[test]
+    fn test_job_queue_update_missing_job() {
+        let queue = JobQueue::new();
+        let result = queue.update(Uuid::new_v4(), |job| job.start());
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_fail_schedules_retry_before_dead_letter() {
+        let mut job = Job::new("test.pdf", ConvertOptions::default());
+        job.fail("transient error".to_string());
+
+        assert_eq!(job.status, JobStatus::Retrying);
+        assert_eq!(job.attempts, 1);
+        assert!(job.next_attempt_at.is_some());
+    }
+
+    #[test]
+    fn test_fail_dead_letters_after_max_attempts() {
+        let options = ConvertOptions {
+            max_attempts: 2,
+            ..ConvertOptions::default()
+        };
+        let mut job = Job::new("test.pdf", options);
+
+        job.fail("first failure".to_string());
+        assert_eq!(job.status, JobStatus::Retrying);
+
+        job.fail("second failure".to_string());
+        assert_eq!(job.status, JobStatus::DeadLettered);
+        assert_eq!(job.attempts, 2);
+    }
+
+    #[test]
+    fn test_promote_ready_retries() {
+        let queue = JobQueue::new();
+        // Without jitter the first retry is a fixed 2 s out
+        let options = ConvertOptions {
+            retry_jitter: false,
+            ..ConvertOptions::default()
+        };
+        let job = Job::new("test.pdf", options);
+        let job_id = job.id;
+        queue.submit(job);
+
+        queue.update(job_id, |j| j.fail("transient error".to_string()));
+        assert_eq!(queue.get(job_id).unwrap().status, JobStatus::Retrying);
+
+        // Not due yet
+        assert!(queue.promote_ready_retries().is_empty());
+
+        // Force the retry into the past so it becomes eligible
+        queue.update(job_id, |j| {
+            j.next_attempt_at = Some(Utc::now() - chrono::Duration::seconds(1));
+        });
+
+        let promoted = queue.promote_ready_retries();
+        assert_eq!(promoted.len(), 1);
+        assert_eq!(queue.get(job_id).unwrap().status, JobStatus::Queued);
+    }
+}
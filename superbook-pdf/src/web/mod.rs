@@ -23,11 +23,13 @@
 mod auth;
 mod batch;
 mod cors;
+mod dump;
 mod job;
 mod metrics;
 mod persistence;
 mod rate_limit;
 mod routes;
+mod schedule;
 mod server;
 mod shutdown;
 mod websocket;
@@ -36,16 +38,25 @@ mod worker;
 pub use auth::{ApiKey, AuthConfig, AuthError, AuthManager, AuthResult, AuthStatusResponse, Scope, extract_api_key};
 pub use batch::{BatchJob, BatchProgress, BatchQueue, BatchStatus, Priority};
 pub use cors::CorsConfig;
-pub use job::{ConvertOptions, Job, JobQueue, JobStatus, Progress};
+pub use dump::{DumpMeta, QueueDump, RestoreSummary, DUMP_VERSION};
+pub use job::{
+    ConvertOptions, Job, JobDispatcher, JobQueue, JobStatus, Progress, WebConvertOptions,
+    WebProgress,
+};
 pub use metrics::{BatchStatistics, JobStatistics, MetricsCollector, ServerInfo, StatsResponse, SystemMetrics};
 pub use persistence::{HistoryQuery, HistoryResponse, JsonJobStore, JobStore, PersistenceConfig, RecoveryManager, RecoveryResult, RetryResponse, StorageBackend, StoreError};
 pub use rate_limit::{RateLimitConfig, RateLimitError, RateLimitResult, RateLimiter, RateLimitStatus};
+pub use routes::{
+    create_schedule, delete_schedule, dump_queues, get_schedule, list_schedules, restore_queues,
+    set_schedule_enabled,
+};
+pub use schedule::{CronError, CronScheduler, FileObservation, ScheduledJob};
 pub use server::{ServerConfig, WebServer};
 pub use shutdown::{ShutdownConfig, ShutdownCoordinator, ShutdownResult, ShutdownSignal, graceful_shutdown, wait_for_shutdown_signal};
 pub use websocket::{
     generate_preview_base64, preview_stage, WsBroadcaster, WsMessage, PREVIEW_WIDTH,
 };
-pub use worker::{JobWorker, WorkerPool};
+pub use worker::{JobWorker, Scheduler, WorkerPool};
 
 /// Default server port
 pub const DEFAULT_PORT: u16 = 8080;
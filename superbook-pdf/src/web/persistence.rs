@@ -0,0 +1,874 @@
+//! Persistent storage for the job and batch queues
+//!
+//! [`JobQueue`](super::job::JobQueue) and [`BatchQueue`](super::batch::BatchQueue)
+//! are in-memory by default. A [`JobStore`] lets either queue write through
+//! to disk (JSON files or SQLite) so jobs started before a restart can be
+//! reloaded rather than lost. [`RecoveryManager`] reconciles jobs that were
+//! still `Processing` when the previous run stopped.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::batch::{BatchJob, BatchStatus, Priority};
+use super::job::{Job, JobQueue, JobStatus};
+use super::schedule::ScheduledJob;
+
+/// Errors that can occur while reading or writing persisted jobs
+#[derive(Debug, Error)]
+pub enum StoreError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("sqlite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+
+    #[error("job not found: {0}")]
+    NotFound(Uuid),
+
+    #[error("unsupported dump version: {0}")]
+    UnsupportedVersion(u32),
+
+    #[error("invalid stored value: {0}")]
+    Invalid(String),
+}
+
+/// Backing storage for a [`JobStore`]
+#[derive(Debug, Clone)]
+pub enum StorageBackend {
+    /// Keep jobs in memory only; nothing survives a restart
+    Memory,
+    /// One JSON file per job/batch under this directory
+    Json(PathBuf),
+    /// A single SQLite database file
+    Sqlite(PathBuf),
+}
+
+/// Configuration for how the web server persists jobs and batches
+#[derive(Debug, Clone)]
+pub struct PersistenceConfig {
+    pub backend: StorageBackend,
+    /// Whether to run [`RecoveryManager::recover`] against the queue on startup
+    pub auto_recover: bool,
+}
+
+impl Default for PersistenceConfig {
+    fn default() -> Self {
+        Self {
+            backend: StorageBackend::Memory,
+            auto_recover: true,
+        }
+    }
+}
+
+/// A page of job history, most recent first
+pub struct HistoryQuery {
+    pub status: Option<JobStatus>,
+    pub limit: usize,
+    pub offset: usize,
+}
+
+impl Default for HistoryQuery {
+    fn default() -> Self {
+        Self {
+            status: None,
+            limit: 50,
+            offset: 0,
+        }
+    }
+}
+
+/// Result of a [`HistoryQuery`]
+pub struct HistoryResponse {
+    pub jobs: Vec<Job>,
+    pub total: usize,
+    pub limit: usize,
+    pub offset: usize,
+}
+
+/// Result of retrying a failed job
+pub struct RetryResponse {
+    pub job_id: Uuid,
+    pub retried: bool,
+    pub message: String,
+}
+
+/// Persists jobs and batches so a queue can be reconstructed across restarts
+pub trait JobStore: Send + Sync {
+    fn save_job(&self, job: &Job) -> Result<(), StoreError>;
+    fn load_jobs(&self) -> Result<Vec<Job>, StoreError>;
+    fn delete_job(&self, id: Uuid) -> Result<(), StoreError>;
+
+    fn save_batch(&self, batch: &BatchJob) -> Result<(), StoreError>;
+    fn load_batches(&self) -> Result<Vec<BatchJob>, StoreError>;
+    fn delete_batch(&self, id: Uuid) -> Result<(), StoreError>;
+
+    fn save_schedule(&self, schedule: &ScheduledJob) -> Result<(), StoreError>;
+    fn load_schedules(&self) -> Result<Vec<ScheduledJob>, StoreError>;
+    fn delete_schedule(&self, id: Uuid) -> Result<(), StoreError>;
+
+    /// Paginated, filtered view over stored jobs, most recently created first
+    fn query_history(&self, query: &HistoryQuery) -> Result<HistoryResponse, StoreError> {
+        let mut jobs = self.load_jobs()?;
+        jobs.retain(|job| query.status.map_or(true, |s| job.status == s));
+        jobs.sort_by_key(|job| std::cmp::Reverse(job.created_at));
+
+        let total = jobs.len();
+        let page = jobs.into_iter().skip(query.offset).take(query.limit).collect();
+
+        Ok(HistoryResponse {
+            jobs: page,
+            total,
+            limit: query.limit,
+            offset: query.offset,
+        })
+    }
+}
+
+/// A `JobStore` that keeps nothing; used for the `Memory` backend
+struct NullJobStore;
+
+impl JobStore for NullJobStore {
+    fn save_job(&self, _job: &Job) -> Result<(), StoreError> {
+        Ok(())
+    }
+    fn load_jobs(&self) -> Result<Vec<Job>, StoreError> {
+        Ok(Vec::new())
+    }
+    fn delete_job(&self, _id: Uuid) -> Result<(), StoreError> {
+        Ok(())
+    }
+    fn save_batch(&self, _batch: &BatchJob) -> Result<(), StoreError> {
+        Ok(())
+    }
+    fn load_batches(&self) -> Result<Vec<BatchJob>, StoreError> {
+        Ok(Vec::new())
+    }
+    fn delete_batch(&self, _id: Uuid) -> Result<(), StoreError> {
+        Ok(())
+    }
+    fn save_schedule(&self, _schedule: &ScheduledJob) -> Result<(), StoreError> {
+        Ok(())
+    }
+    fn load_schedules(&self) -> Result<Vec<ScheduledJob>, StoreError> {
+        Ok(Vec::new())
+    }
+    fn delete_schedule(&self, _id: Uuid) -> Result<(), StoreError> {
+        Ok(())
+    }
+}
+
+/// Build the `JobStore` described by a [`PersistenceConfig`]
+pub fn open_store(config: &PersistenceConfig) -> Result<std::sync::Arc<dyn JobStore>, StoreError> {
+    match &config.backend {
+        StorageBackend::Memory => Ok(std::sync::Arc::new(NullJobStore)),
+        StorageBackend::Json(dir) => Ok(std::sync::Arc::new(JsonJobStore::new(dir)?)),
+        StorageBackend::Sqlite(path) => Ok(std::sync::Arc::new(SqliteJobStore::open(path)?)),
+    }
+}
+
+/// One JSON file per job/batch under a directory
+pub struct JsonJobStore {
+    dir: PathBuf,
+}
+
+impl JsonJobStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Result<Self, StoreError> {
+        let dir = dir.into();
+        fs::create_dir_all(dir.join("jobs"))?;
+        fs::create_dir_all(dir.join("batches"))?;
+        fs::create_dir_all(dir.join("schedules"))?;
+        Ok(Self { dir })
+    }
+
+    fn job_path(&self, id: Uuid) -> PathBuf {
+        self.dir.join("jobs").join(format!("{id}.json"))
+    }
+
+    fn batch_path(&self, id: Uuid) -> PathBuf {
+        self.dir.join("batches").join(format!("{id}.json"))
+    }
+
+    fn schedule_path(&self, id: Uuid) -> PathBuf {
+        self.dir.join("schedules").join(format!("{id}.json"))
+    }
+
+    fn read_all<T: serde::de::DeserializeOwned>(dir: &Path) -> Result<Vec<T>, StoreError> {
+        let mut items = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let data = fs::read(&path)?;
+            items.push(serde_json::from_slice(&data)?);
+        }
+        Ok(items)
+    }
+}
+
+impl JobStore for JsonJobStore {
+    fn save_job(&self, job: &Job) -> Result<(), StoreError> {
+        fs::write(self.job_path(job.id), serde_json::to_vec(job)?)?;
+        Ok(())
+    }
+
+    fn load_jobs(&self) -> Result<Vec<Job>, StoreError> {
+        Self::read_all(&self.dir.join("jobs"))
+    }
+
+    fn delete_job(&self, id: Uuid) -> Result<(), StoreError> {
+        let path = self.job_path(id);
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    fn save_batch(&self, batch: &BatchJob) -> Result<(), StoreError> {
+        fs::write(self.batch_path(batch.id), serde_json::to_vec(batch)?)?;
+        Ok(())
+    }
+
+    fn load_batches(&self) -> Result<Vec<BatchJob>, StoreError> {
+        Self::read_all(&self.dir.join("batches"))
+    }
+
+    fn delete_batch(&self, id: Uuid) -> Result<(), StoreError> {
+        let path = self.batch_path(id);
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    fn save_schedule(&self, schedule: &ScheduledJob) -> Result<(), StoreError> {
+        fs::write(self.schedule_path(schedule.id), serde_json::to_vec(schedule)?)?;
+        Ok(())
+    }
+
+    fn load_schedules(&self) -> Result<Vec<ScheduledJob>, StoreError> {
+        Self::read_all(&self.dir.join("schedules"))
+    }
+
+    fn delete_schedule(&self, id: Uuid) -> Result<(), StoreError> {
+        let path = self.schedule_path(id);
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+fn job_status_text(status: JobStatus) -> &'static str {
+    match status {
+        JobStatus::Queued => "queued",
+        JobStatus::Processing => "processing",
+        JobStatus::Retrying => "retrying",
+        JobStatus::Completed => "completed",
+        JobStatus::Failed => "failed",
+        JobStatus::DeadLettered => "dead_lettered",
+        JobStatus::Cancelled => "cancelled",
+    }
+}
+
+fn job_status_from_text(text: &str) -> Result<JobStatus, StoreError> {
+    Ok(match text {
+        "queued" => JobStatus::Queued,
+        "processing" => JobStatus::Processing,
+        "retrying" => JobStatus::Retrying,
+        "completed" => JobStatus::Completed,
+        "failed" => JobStatus::Failed,
+        "dead_lettered" => JobStatus::DeadLettered,
+        "cancelled" => JobStatus::Cancelled,
+        other => return Err(StoreError::Invalid(format!("unknown job status: {other}"))),
+    })
+}
+
+fn batch_status_text(status: BatchStatus) -> &'static str {
+    match status {
+        BatchStatus::Queued => "queued",
+        BatchStatus::Processing => "processing",
+        BatchStatus::Completed => "completed",
+        BatchStatus::Failed => "failed",
+        BatchStatus::Cancelled => "cancelled",
+    }
+}
+
+fn batch_status_from_text(text: &str) -> Result<BatchStatus, StoreError> {
+    Ok(match text {
+        "queued" => BatchStatus::Queued,
+        "processing" => BatchStatus::Processing,
+        "completed" => BatchStatus::Completed,
+        "failed" => BatchStatus::Failed,
+        "cancelled" => BatchStatus::Cancelled,
+        other => return Err(StoreError::Invalid(format!("unknown batch status: {other}"))),
+    })
+}
+
+fn priority_text(priority: Priority) -> &'static str {
+    match priority {
+        Priority::Low => "low",
+        Priority::Normal => "normal",
+        Priority::High => "high",
+    }
+}
+
+fn priority_from_text(text: &str) -> Result<Priority, StoreError> {
+    Ok(match text {
+        "low" => Priority::Low,
+        "normal" => Priority::Normal,
+        "high" => Priority::High,
+        other => return Err(StoreError::Invalid(format!("unknown priority: {other}"))),
+    })
+}
+
+fn parse_rfc3339(text: &str) -> Result<DateTime<Utc>, StoreError> {
+    DateTime::parse_from_rfc3339(text)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| StoreError::Invalid(e.to_string()))
+}
+
+fn parse_uuid(text: &str) -> Result<Uuid, StoreError> {
+    Uuid::parse_str(text).map_err(|e| StoreError::Invalid(e.to_string()))
+}
+
+/// Raw columns read back from the `jobs` table, before the text-encoded
+/// status/priority/options/timestamps are parsed into a [`Job`]
+struct JobRow {
+    id: String,
+    input_filename: String,
+    status: String,
+    priority: String,
+    options: String,
+    progress: Option<String>,
+    output_path: Option<String>,
+    error: Option<String>,
+    attempts: i64,
+    max_attempts: i64,
+    next_attempt_at: Option<String>,
+    created_at: String,
+    started_at: Option<String>,
+    completed_at: Option<String>,
+}
+
+impl JobRow {
+    fn into_job(self) -> Result<Job, StoreError> {
+        Ok(Job {
+            id: parse_uuid(&self.id)?,
+            input_filename: self.input_filename,
+            options: serde_json::from_str(&self.options)?,
+            priority: priority_from_text(&self.priority)?,
+            status: job_status_from_text(&self.status)?,
+            progress: self.progress.as_deref().map(serde_json::from_str).transpose()?,
+            output_path: self.output_path.map(PathBuf::from),
+            error: self.error,
+            attempts: self.attempts as u32,
+            max_attempts: self.max_attempts as u32,
+            next_attempt_at: self.next_attempt_at.as_deref().map(parse_rfc3339).transpose()?,
+            created_at: parse_rfc3339(&self.created_at)?,
+            started_at: self.started_at.as_deref().map(parse_rfc3339).transpose()?,
+            completed_at: self.completed_at.as_deref().map(parse_rfc3339).transpose()?,
+            cancel_token: Default::default(),
+        })
+    }
+}
+
+const JOB_COLUMNS: &str = "id, input_filename, status, priority, options, progress, \
+     output_path, error, attempts, max_attempts, next_attempt_at, created_at, \
+     started_at, completed_at";
+
+fn row_to_job_row(row: &rusqlite::Row) -> rusqlite::Result<JobRow> {
+    Ok(JobRow {
+        id: row.get(0)?,
+        input_filename: row.get(1)?,
+        status: row.get(2)?,
+        priority: row.get(3)?,
+        options: row.get(4)?,
+        progress: row.get(5)?,
+        output_path: row.get(6)?,
+        error: row.get(7)?,
+        attempts: row.get(8)?,
+        max_attempts: row.get(9)?,
+        next_attempt_at: row.get(10)?,
+        created_at: row.get(11)?,
+        started_at: row.get(12)?,
+        completed_at: row.get(13)?,
+    })
+}
+
+/// A SQLite-backed store with a normalized `jobs` table (one row per job,
+/// one column per field) plus a parallel `batches` table and a `batch_jobs`
+/// join table recording which jobs belong to which batch. Schedules are
+/// still stored as opaque JSON rows, since nothing queries them by field.
+pub struct SqliteJobStore {
+    conn: Mutex<rusqlite::Connection>,
+}
+
+impl SqliteJobStore {
+    pub fn open(path: &Path) -> Result<Self, StoreError> {
+        let conn = rusqlite::Connection::open(path)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS jobs (
+                id TEXT PRIMARY KEY,
+                input_filename TEXT NOT NULL,
+                status TEXT NOT NULL,
+                priority TEXT NOT NULL,
+                options TEXT NOT NULL,
+                progress TEXT,
+                output_path TEXT,
+                error TEXT,
+                attempts INTEGER NOT NULL,
+                max_attempts INTEGER NOT NULL,
+                next_attempt_at TEXT,
+                created_at TEXT NOT NULL,
+                started_at TEXT,
+                completed_at TEXT
+             );
+             CREATE INDEX IF NOT EXISTS idx_jobs_status ON jobs(status);
+
+             CREATE TABLE IF NOT EXISTS batches (
+                id TEXT PRIMARY KEY,
+                status TEXT NOT NULL,
+                priority TEXT NOT NULL,
+                options TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                started_at TEXT,
+                completed_at TEXT
+             );
+
+             CREATE TABLE IF NOT EXISTS batch_jobs (
+                batch_id TEXT NOT NULL,
+                job_id TEXT NOT NULL,
+                PRIMARY KEY (batch_id, job_id)
+             );
+
+             CREATE TABLE IF NOT EXISTS schedules (id TEXT PRIMARY KEY, data TEXT NOT NULL);",
+        )?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn load_table<T: serde::de::DeserializeOwned>(&self, table: &str) -> Result<Vec<T>, StoreError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&format!("SELECT data FROM {table}"))?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+
+        let mut items = Vec::new();
+        for row in rows {
+            items.push(serde_json::from_str(&row?)?);
+        }
+        Ok(items)
+    }
+
+    fn job_ids_for_batch(
+        conn: &rusqlite::Connection,
+        batch_id: &str,
+    ) -> Result<Vec<Uuid>, StoreError> {
+        let mut stmt = conn.prepare("SELECT job_id FROM batch_jobs WHERE batch_id = ?1")?;
+        let rows = stmt.query_map(rusqlite::params![batch_id], |row| row.get::<_, String>(0))?;
+
+        let mut ids = Vec::new();
+        for row in rows {
+            ids.push(parse_uuid(&row?)?);
+        }
+        Ok(ids)
+    }
+}
+
+impl JobStore for SqliteJobStore {
+    fn save_job(&self, job: &Job) -> Result<(), StoreError> {
+        let options = serde_json::to_string(&job.options)?;
+        let progress = job.progress.as_ref().map(serde_json::to_string).transpose()?;
+        let output_path = job.output_path.as_ref().map(|p| p.to_string_lossy().into_owned());
+
+        self.conn.lock().unwrap().execute(
+            &format!(
+                "INSERT INTO jobs ({JOB_COLUMNS})
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
+                 ON CONFLICT(id) DO UPDATE SET
+                    input_filename = excluded.input_filename,
+                    status = excluded.status,
+                    priority = excluded.priority,
+                    options = excluded.options,
+                    progress = excluded.progress,
+                    output_path = excluded.output_path,
+                    error = excluded.error,
+                    attempts = excluded.attempts,
+                    max_attempts = excluded.max_attempts,
+                    next_attempt_at = excluded.next_attempt_at,
+                    created_at = excluded.created_at,
+                    started_at = excluded.started_at,
+                    completed_at = excluded.completed_at"
+            ),
+            rusqlite::params![
+                job.id.to_string(),
+                job.input_filename,
+                job_status_text(job.status),
+                priority_text(job.priority),
+                options,
+                progress,
+                output_path,
+                job.error,
+                job.attempts,
+                job.max_attempts,
+                job.next_attempt_at.map(|t| t.to_rfc3339()),
+                job.created_at.to_rfc3339(),
+                job.started_at.map(|t| t.to_rfc3339()),
+                job.completed_at.map(|t| t.to_rfc3339()),
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn load_jobs(&self) -> Result<Vec<Job>, StoreError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&format!("SELECT {JOB_COLUMNS} FROM jobs"))?;
+        let rows = stmt.query_map([], row_to_job_row)?;
+
+        let mut jobs = Vec::new();
+        for row in rows {
+            jobs.push(row?.into_job()?);
+        }
+        Ok(jobs)
+    }
+
+    fn delete_job(&self, id: Uuid) -> Result<(), StoreError> {
+        self.conn
+            .lock()
+            .unwrap()
+            .execute("DELETE FROM jobs WHERE id = ?1", rusqlite::params![id.to_string()])?;
+        Ok(())
+    }
+
+    fn save_batch(&self, batch: &BatchJob) -> Result<(), StoreError> {
+        let options = serde_json::to_string(&batch.options)?;
+        let conn = self.conn.lock().unwrap();
+
+        conn.execute(
+            "INSERT INTO batches (id, status, priority, options, created_at, started_at, completed_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT(id) DO UPDATE SET
+                status = excluded.status,
+                priority = excluded.priority,
+                options = excluded.options,
+                created_at = excluded.created_at,
+                started_at = excluded.started_at,
+                completed_at = excluded.completed_at",
+            rusqlite::params![
+                batch.id.to_string(),
+                batch_status_text(batch.status),
+                priority_text(batch.priority),
+                options,
+                batch.created_at.to_rfc3339(),
+                batch.started_at.map(|t| t.to_rfc3339()),
+                batch.completed_at.map(|t| t.to_rfc3339()),
+            ],
+        )?;
+
+        // The join table is rebuilt wholesale on every save rather than
+        // diffed, since a batch's job list only grows once at creation
+        conn.execute(
+            "DELETE FROM batch_jobs WHERE batch_id = ?1",
+            rusqlite::params![batch.id.to_string()],
+        )?;
+        for job_id in &batch.job_ids {
+            conn.execute(
+                "INSERT INTO batch_jobs (batch_id, job_id) VALUES (?1, ?2)",
+                rusqlite::params![batch.id.to_string(), job_id.to_string()],
+            )?;
+        }
+        Ok(())
+    }
+
+    fn load_batches(&self) -> Result<Vec<BatchJob>, StoreError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, status, priority, options, created_at, started_at, completed_at FROM batches",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
+                row.get::<_, Option<String>>(5)?,
+                row.get::<_, Option<String>>(6)?,
+            ))
+        })?;
+
+        let mut batches = Vec::new();
+        for row in rows {
+            let (id, status, priority, options, created_at, started_at, completed_at) = row?;
+            batches.push(BatchJob {
+                id: parse_uuid(&id)?,
+                options: serde_json::from_str(&options)?,
+                priority: priority_from_text(&priority)?,
+                status: batch_status_from_text(&status)?,
+                job_ids: Self::job_ids_for_batch(&conn, &id)?,
+                created_at: parse_rfc3339(&created_at)?,
+                started_at: started_at.as_deref().map(parse_rfc3339).transpose()?,
+                completed_at: completed_at.as_deref().map(parse_rfc3339).transpose()?,
+            });
+        }
+        Ok(batches)
+    }
+
+    fn delete_batch(&self, id: Uuid) -> Result<(), StoreError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM batches WHERE id = ?1",
+            rusqlite::params![id.to_string()],
+        )?;
+        conn.execute(
+            "DELETE FROM batch_jobs WHERE batch_id = ?1",
+            rusqlite::params![id.to_string()],
+        )?;
+        Ok(())
+    }
+
+    fn save_schedule(&self, schedule: &ScheduledJob) -> Result<(), StoreError> {
+        let data = serde_json::to_string(schedule)?;
+        self.conn.lock().unwrap().execute(
+            "INSERT INTO schedules (id, data) VALUES (?1, ?2)
+             ON CONFLICT(id) DO UPDATE SET data = excluded.data",
+            rusqlite::params![schedule.id.to_string(), data],
+        )?;
+        Ok(())
+    }
+
+    fn load_schedules(&self) -> Result<Vec<ScheduledJob>, StoreError> {
+        self.load_table("schedules")
+    }
+
+    fn delete_schedule(&self, id: Uuid) -> Result<(), StoreError> {
+        self.conn.lock().unwrap().execute(
+            "DELETE FROM schedules WHERE id = ?1",
+            rusqlite::params![id.to_string()],
+        )?;
+        Ok(())
+    }
+
+    /// Pushes the status filter, ordering, and pagination down to SQL
+    /// instead of deserializing every row to filter in Rust
+    fn query_history(&self, query: &HistoryQuery) -> Result<HistoryResponse, StoreError> {
+        let conn = self.conn.lock().unwrap();
+        let status_filter = query.status.map(job_status_text);
+
+        let total: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM jobs WHERE (?1 IS NULL OR status = ?1)",
+            rusqlite::params![status_filter],
+            |row| row.get(0),
+        )?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {JOB_COLUMNS} FROM jobs
+             WHERE (?1 IS NULL OR status = ?1)
+             ORDER BY created_at DESC
+             LIMIT ?2 OFFSET ?3"
+        ))?;
+        let rows = stmt.query_map(
+            rusqlite::params![status_filter, query.limit as i64, query.offset as i64],
+            row_to_job_row,
+        )?;
+
+        let mut jobs = Vec::new();
+        for row in rows {
+            jobs.push(row?.into_job()?);
+        }
+
+        Ok(HistoryResponse {
+            jobs,
+            total: total as usize,
+            limit: query.limit,
+            offset: query.offset,
+        })
+    }
+}
+
+/// Outcome of reconciling a queue against the previous run's state
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RecoveryResult {
+    /// Jobs that were `Processing` and have been requeued
+    pub requeued: usize,
+    /// Jobs that could not be recovered and were marked `Failed`
+    pub failed: usize,
+}
+
+impl RecoveryResult {
+    pub fn recovered(&self) -> usize {
+        self.requeued + self.failed
+    }
+}
+
+/// Reconciles jobs left in a non-terminal state by an unclean shutdown
+pub struct RecoveryManager;
+
+impl RecoveryManager {
+    /// Requeue any job still marked `Processing`, since no worker survived
+    /// the restart to finish it
+    pub fn recover(queue: &JobQueue) -> RecoveryResult {
+        let mut result = RecoveryResult::default();
+
+        for job in queue.list() {
+            if job.status == JobStatus::Processing {
+                queue.update(job.id, |j| {
+                    j.status = JobStatus::Queued;
+                    j.started_at = None;
+                    j.progress = None;
+                });
+                result.requeued += 1;
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::web::job::ConvertOptions;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_json_job_store_round_trip() {
+        let dir = tempdir().unwrap();
+        let store = JsonJobStore::new(dir.path()).unwrap();
+
+        let job = Job::new("test.pdf", ConvertOptions::default());
+        let job_id = job.id;
+        store.save_job(&job).unwrap();
+
+        let loaded = store.load_jobs().unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].id, job_id);
+
+        store.delete_job(job_id).unwrap();
+        assert!(store.load_jobs().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_sqlite_job_store_round_trip() {
+        let dir = tempdir().unwrap();
+        let store = SqliteJobStore::open(&dir.path().join("jobs.sqlite")).unwrap();
+
+        let job = Job::new("test.pdf", ConvertOptions::default());
+        let job_id = job.id;
+        store.save_job(&job).unwrap();
+
+        let loaded = store.load_jobs().unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].id, job_id);
+
+        store.delete_job(job_id).unwrap();
+        assert!(store.load_jobs().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_job_queue_survives_restart() {
+        let dir = tempdir().unwrap();
+        let store: std::sync::Arc<dyn JobStore> =
+            std::sync::Arc::new(SqliteJobStore::open(&dir.path().join("jobs.sqlite")).unwrap());
+
+        let job_id = {
+            let queue = JobQueue::with_store(store.clone());
+            let job = Job::new("test.pdf", ConvertOptions::default());
+            let job_id = job.id;
+            queue.submit(job);
+            job_id
+        };
+
+        let restarted = JobQueue::with_store(store);
+        assert!(restarted.get(job_id).is_some());
+    }
+
+    #[test]
+    fn test_recovery_requeues_processing_jobs() {
+        let queue = JobQueue::new();
+        let job = Job::new("test.pdf", ConvertOptions::default());
+        let job_id = job.id;
+        queue.submit(job);
+        queue.update(job_id, |j| j.start());
+
+        let result = RecoveryManager::recover(&queue);
+        assert_eq!(result.requeued, 1);
+        assert_eq!(result.recovered(), 1);
+
+        let job = queue.get(job_id).unwrap();
+        assert_eq!(job.status, JobStatus::Queued);
+        assert!(job.started_at.is_none());
+    }
+
+    #[test]
+    fn test_history_query_pagination() {
+        let dir = tempdir().unwrap();
+        let store = JsonJobStore::new(dir.path()).unwrap();
+
+        for i in 0..5 {
+            store
+                .save_job(&Job::new(&format!("file{i}.pdf"), ConvertOptions::default()))
+                .unwrap();
+        }
+
+        let query = HistoryQuery {
+            status: None,
+            limit: 2,
+            offset: 0,
+        };
+        let response = store.query_history(&query).unwrap();
+        assert_eq!(response.jobs.len(), 2);
+        assert_eq!(response.total, 5);
+    }
+
+    #[test]
+    fn test_sqlite_history_query_filters_by_status_in_sql() {
+        let dir = tempdir().unwrap();
+        let store = SqliteJobStore::open(&dir.path().join("jobs.sqlite")).unwrap();
+
+        let mut completed = Job::new("done.pdf", ConvertOptions::default());
+        completed.status = JobStatus::Completed;
+        store.save_job(&completed).unwrap();
+        store.save_job(&Job::new("queued.pdf", ConvertOptions::default())).unwrap();
+
+        let query = HistoryQuery {
+            status: Some(JobStatus::Completed),
+            limit: 50,
+            offset: 0,
+        };
+        let response = store.query_history(&query).unwrap();
+        assert_eq!(response.total, 1);
+        assert_eq!(response.jobs[0].id, completed.id);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_batch_store_round_trip_preserves_job_ids() {
+        let dir = tempdir().unwrap();
+        let job_queue = JobQueue::new();
+        let store: std::sync::Arc<dyn JobStore> =
+            std::sync::Arc::new(SqliteJobStore::open(&dir.path().join("jobs.sqlite")).unwrap());
+
+        let mut batch = BatchJob::new(ConvertOptions::default(), Priority::High);
+        let batch_queue = crate::web::batch::BatchQueue::with_store(job_queue, store.clone()).await;
+        batch_queue.create_jobs(&mut batch, &["a.pdf".to_string(), "b.pdf".to_string()]);
+        store.save_batch(&batch).unwrap();
+
+        let loaded = store.load_batches().unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].priority, Priority::High);
+        let mut job_ids = loaded[0].job_ids.clone();
+        job_ids.sort();
+        let mut expected = batch.job_ids.clone();
+        expected.sort();
+        assert_eq!(job_ids, expected);
+    }
+}
@@ -0,0 +1,111 @@
+//! REST endpoint handlers for queue dump/restore and schedule CRUD
+//!
+//! No HTTP framework has been wired into this crate yet (see
+//! [`WebServer`](super::server::WebServer)), so these are framework-agnostic
+//! handler functions: thin wrappers around [`BatchQueue`]/[`CronScheduler`]
+//! that take already-parsed arguments and return plain `Result`s. Mounting
+//! them behind an actual router, once one is chosen, is a matter of binding
+//! each fn to a method + path rather than writing new logic.
+
+use std::path::Path;
+
+use uuid::Uuid;
+
+use super::batch::BatchQueue;
+use super::dump::RestoreSummary;
+use super::persistence::StoreError;
+use super::schedule::{CronError, CronScheduler, ScheduledJob};
+
+/// `POST /admin/dump` — snapshot every job and batch to `path`
+pub async fn dump_queues(batch_queue: &BatchQueue, path: &Path) -> Result<(), StoreError> {
+    batch_queue.dump(path).await
+}
+
+/// `POST /admin/restore` — reload jobs and batches from a dump previously
+/// written by [`dump_queues`]
+pub async fn restore_queues(
+    batch_queue: &BatchQueue,
+    path: &Path,
+) -> Result<RestoreSummary, StoreError> {
+    batch_queue.restore(path).await
+}
+
+/// `GET /schedules` — list every registered schedule
+pub fn list_schedules(scheduler: &CronScheduler) -> Vec<ScheduledJob> {
+    scheduler.list()
+}
+
+/// `POST /schedules` — validate and register a new schedule
+pub fn create_schedule(
+    scheduler: &CronScheduler,
+    schedule: ScheduledJob,
+) -> Result<Uuid, CronError> {
+    scheduler.register(schedule)
+}
+
+/// `GET /schedules/:id` — fetch a single schedule
+pub fn get_schedule(scheduler: &CronScheduler, id: Uuid) -> Option<ScheduledJob> {
+    scheduler.get(id)
+}
+
+/// `PATCH /schedules/:id` — enable or disable a schedule
+pub fn set_schedule_enabled(
+    scheduler: &CronScheduler,
+    id: Uuid,
+    enabled: bool,
+) -> Option<ScheduledJob> {
+    scheduler.set_enabled(id, enabled)
+}
+
+/// `DELETE /schedules/:id` — remove a schedule and its debounce bookkeeping
+pub fn delete_schedule(scheduler: &CronScheduler, id: Uuid) -> Option<ScheduledJob> {
+    scheduler.remove(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::web::batch::{BatchJob, Priority};
+    use crate::web::job::{ConvertOptions, JobQueue};
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_dump_then_restore_round_trip() {
+        let dir = tempdir().unwrap();
+        let dump_path = dir.path().join("queues.json");
+
+        let job_queue = JobQueue::new();
+        let batch_queue = BatchQueue::new(job_queue.clone());
+        let mut batch = BatchJob::new(ConvertOptions::default(), Priority::Normal);
+        batch_queue.create_jobs(&mut batch, &["a.pdf".to_string()]);
+        batch_queue.submit(batch).await;
+
+        dump_queues(&batch_queue, &dump_path).await.unwrap();
+
+        let restored_job_queue = JobQueue::new();
+        let restored_batch_queue = BatchQueue::new(restored_job_queue);
+        let summary = restore_queues(&restored_batch_queue, &dump_path).await.unwrap();
+        assert_eq!(summary.jobs_restored, 1);
+        assert_eq!(summary.batches_restored, 1);
+    }
+
+    #[test]
+    fn test_schedule_crud_handlers() {
+        use crate::web::batch::Priority;
+        use crate::web::job::ConvertOptions;
+
+        let scheduler = CronScheduler::new(JobQueue::new());
+        let schedule = ScheduledJob::new("* * * * *", ConvertOptions::default(), Priority::Normal)
+            .with_default_input("recurring.pdf");
+
+        let id = create_schedule(&scheduler, schedule).unwrap();
+        assert_eq!(list_schedules(&scheduler).len(), 1);
+        assert!(get_schedule(&scheduler, id).is_some());
+
+        let disabled = set_schedule_enabled(&scheduler, id, false).unwrap();
+        assert!(!disabled.enabled);
+
+        assert!(delete_schedule(&scheduler, id).is_some());
+        assert!(get_schedule(&scheduler, id).is_none());
+    }
+}
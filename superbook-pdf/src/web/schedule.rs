@@ -0,0 +1,834 @@
+//! Recurring and file-watch triggered conversion jobs
+//!
+//! [`ScheduledJob`] describes a conversion that should run on a cron-like
+//! cadence and/or whenever new files land in a watched directory, rather
+//! than only in response to a one-shot [`JobQueue::submit`]. [`CronScheduler`]
+//! parses each schedule's cron expression, ticks on a `tokio::time::interval`,
+//! and enqueues a fresh [`Job`] every time a schedule comes due. It's a
+//! distinct type from [`super::worker::Scheduler`], which dispatches jobs
+//! already in the queue onto worker slots — this one decides *when new jobs
+//! get created* in the first place.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use chrono::{DateTime, Datelike, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::batch::Priority;
+use super::job::{ConvertOptions, Job, JobQueue, JobStatus};
+use super::persistence::JobStore;
+
+/// Errors parsing a 5-field cron expression
+#[derive(Debug, Error)]
+pub enum CronError {
+    #[error("expected 5 whitespace-separated fields (minute hour day-of-month month day-of-week), got {0}")]
+    WrongFieldCount(usize),
+
+    #[error("invalid field {field:?}: {reason}")]
+    InvalidField { field: String, reason: String },
+}
+
+/// One field of a parsed cron expression; `None` means "every value" (`*`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CronField(Option<Vec<u32>>);
+
+impl CronField {
+    fn matches(&self, value: u32) -> bool {
+        match &self.0 {
+            None => true,
+            Some(values) => values.contains(&value),
+        }
+    }
+}
+
+fn parse_cron_field(raw: &str, min: u32, max: u32) -> Result<CronField, CronError> {
+    if raw == "*" {
+        return Ok(CronField(None));
+    }
+
+    let invalid = |reason: String| CronError::InvalidField {
+        field: raw.to_string(),
+        reason,
+    };
+
+    let mut values = Vec::new();
+    for part in raw.split(',') {
+        let (range_part, step) = match part.split_once('/') {
+            Some((r, s)) => (
+                r,
+                s.parse::<u32>()
+                    .map_err(|_| invalid(format!("invalid step {s:?}")))?,
+            ),
+            None => (part, 1),
+        };
+
+        let (lo, hi) = if range_part == "*" {
+            (min, max)
+        } else if let Some((a, b)) = range_part.split_once('-') {
+            let lo = a
+                .parse::<u32>()
+                .map_err(|_| invalid(format!("invalid range start {a:?}")))?;
+            let hi = b
+                .parse::<u32>()
+                .map_err(|_| invalid(format!("invalid range end {b:?}")))?;
+            (lo, hi)
+        } else {
+            let v = range_part
+                .parse::<u32>()
+                .map_err(|_| invalid(format!("invalid value {range_part:?}")))?;
+            (v, v)
+        };
+
+        if step == 0 {
+            return Err(invalid("step cannot be 0".to_string()));
+        }
+        if lo < min || hi > max || lo > hi {
+            return Err(invalid(format!("out of range {min}-{max}")));
+        }
+
+        let mut v = lo;
+        while v <= hi {
+            values.push(v);
+            v += step;
+        }
+    }
+
+    values.sort_unstable();
+    values.dedup();
+    Ok(CronField(Some(values)))
+}
+
+/// A parsed 5-field cron expression (minute hour day-of-month month
+/// day-of-week, day-of-week `0` = Sunday), evaluated minute-by-minute
+#[derive(Debug, Clone)]
+struct CronSchedule {
+    minute: CronField,
+    hour: CronField,
+    day_of_month: CronField,
+    month: CronField,
+    day_of_week: CronField,
+}
+
+impl CronSchedule {
+    fn parse(expr: &str) -> Result<Self, CronError> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(CronError::WrongFieldCount(fields.len()));
+        }
+        Ok(Self {
+            minute: parse_cron_field(fields[0], 0, 59)?,
+            hour: parse_cron_field(fields[1], 0, 23)?,
+            day_of_month: parse_cron_field(fields[2], 1, 31)?,
+            month: parse_cron_field(fields[3], 1, 12)?,
+            day_of_week: parse_cron_field(fields[4], 0, 6)?,
+        })
+    }
+
+    fn matches(&self, dt: DateTime<Utc>) -> bool {
+        self.minute.matches(dt.minute())
+            && self.hour.matches(dt.hour())
+            && self.day_of_month.matches(dt.day())
+            && self.month.matches(dt.month())
+            && self.day_of_week.matches(dt.weekday().num_days_from_sunday())
+    }
+
+    /// Scan forward minute-by-minute, capped at a year out, for the next
+    /// time this schedule is due strictly after `after`
+    fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut candidate = after
+            .with_second(0)?
+            .with_nanosecond(0)?
+            .checked_add_signed(chrono::Duration::minutes(1))?;
+
+        for _ in 0..(366 * 24 * 60) {
+            if self.matches(candidate) {
+                return Some(candidate);
+            }
+            candidate = candidate.checked_add_signed(chrono::Duration::minutes(1))?;
+        }
+        None
+    }
+}
+
+/// A conversion that runs automatically on a cron-like cadence and/or when
+/// files appear in a watched directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledJob {
+    pub id: Uuid,
+    pub cron: String,
+    pub watch_dir: Option<PathBuf>,
+    /// Input used for a plain cron run; ignored when `watch_dir` is set,
+    /// which derives one job per matched file instead
+    pub default_input: Option<String>,
+    pub options: ConvertOptions,
+    pub priority: Priority,
+    pub enabled: bool,
+    pub last_run: Option<DateTime<Utc>>,
+    pub next_run: Option<DateTime<Utc>>,
+    /// Ids of every job created by the run still in flight for this
+    /// schedule; a due tick is skipped while any of these points at a
+    /// non-terminal job, so overlapping runs never pile up. A watch-dir
+    /// tick can create more than one job at once, so *all* of them — not
+    /// just the last — have to finish before the schedule fires again.
+    #[serde(default)]
+    pub active_job_ids: Vec<Uuid>,
+    /// Filenames already turned into a job for a watched directory,
+    /// persisted so a restart doesn't resubmit files this scheduler already
+    /// converted
+    #[serde(default)]
+    pub converted_files: HashSet<PathBuf>,
+    /// Per-file size/mtime last observed for a watched directory's debounce
+    /// check, persisted so a restart doesn't lose "still being written" state
+    #[serde(default)]
+    pub watch_observations: HashMap<PathBuf, FileObservation>,
+}
+
+impl ScheduledJob {
+    /// Create an enabled schedule that does not run until it is registered;
+    /// `next_run` is computed by [`CronScheduler::register`], which also
+    /// validates `cron`
+    pub fn new(cron: impl Into<String>, options: ConvertOptions, priority: Priority) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            cron: cron.into(),
+            watch_dir: None,
+            default_input: None,
+            options,
+            priority,
+            enabled: true,
+            last_run: None,
+            next_run: None,
+            active_job_ids: Vec::new(),
+            converted_files: HashSet::new(),
+            watch_observations: HashMap::new(),
+        }
+    }
+
+    pub fn with_watch_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.watch_dir = Some(dir.into());
+        self
+    }
+
+    pub fn with_default_input(mut self, input: impl Into<String>) -> Self {
+        self.default_input = Some(input.into());
+        self
+    }
+}
+
+/// Snapshot of a watched file used to tell a finished copy from one still
+/// being written
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FileObservation {
+    modified: SystemTime,
+    len: u64,
+}
+
+/// Parses cron expressions and, on a `tokio::time::interval` tick loop,
+/// enqueues a fresh [`Job`] for every schedule that's come due
+pub struct CronScheduler {
+    job_queue: JobQueue,
+    schedules: Mutex<HashMap<Uuid, ScheduledJob>>,
+    parsed: Mutex<HashMap<Uuid, CronSchedule>>,
+    /// Per-schedule, per-file debounce state for watched directories
+    watch_state: Mutex<HashMap<Uuid, HashMap<PathBuf, FileObservation>>>,
+    /// Per-schedule set of files already turned into a job, so a directory
+    /// scan never resubmits the same input twice
+    converted: Mutex<HashMap<Uuid, HashSet<PathBuf>>>,
+    store: Option<Arc<dyn JobStore>>,
+}
+
+impl CronScheduler {
+    /// Create an empty, non-persistent scheduler over `job_queue`
+    pub fn new(job_queue: JobQueue) -> Self {
+        Self {
+            job_queue,
+            schedules: Mutex::new(HashMap::new()),
+            parsed: Mutex::new(HashMap::new()),
+            watch_state: Mutex::new(HashMap::new()),
+            converted: Mutex::new(HashMap::new()),
+            store: None,
+        }
+    }
+
+    /// Create a scheduler backed by `store`, loading any schedules left
+    /// over from a previous run. Schedules whose cron expression no longer
+    /// parses are loaded but left un-runnable (their `next_run` is cleared)
+    /// rather than dropped, so an operator can inspect them via `list` and
+    /// replace them via `register`.
+    pub fn with_store(job_queue: JobQueue, store: Arc<dyn JobStore>) -> Self {
+        let loaded = store.load_schedules().unwrap_or_default();
+        let mut schedules = HashMap::new();
+        let mut parsed = HashMap::new();
+        let mut watch_state = HashMap::new();
+        let mut converted = HashMap::new();
+        for mut schedule in loaded {
+            match CronSchedule::parse(&schedule.cron) {
+                Ok(cron) => {
+                    parsed.insert(schedule.id, cron);
+                }
+                Err(_) => schedule.next_run = None,
+            }
+            if !schedule.watch_observations.is_empty() {
+                watch_state.insert(schedule.id, schedule.watch_observations.clone());
+            }
+            if !schedule.converted_files.is_empty() {
+                converted.insert(schedule.id, schedule.converted_files.clone());
+            }
+            schedules.insert(schedule.id, schedule);
+        }
+        Self {
+            job_queue,
+            schedules: Mutex::new(schedules),
+            parsed: Mutex::new(parsed),
+            watch_state: Mutex::new(watch_state),
+            converted: Mutex::new(converted),
+            store: Some(store),
+        }
+    }
+
+    fn persist(&self, schedule: &ScheduledJob) {
+        if let Some(store) = &self.store {
+            let _ = store.save_schedule(schedule);
+        }
+    }
+
+    /// Validate `schedule.cron`, compute its first `next_run`, and register
+    /// it for dispatch. Exposed as a REST handler via
+    /// [`super::routes::create_schedule`].
+    pub fn register(&self, mut schedule: ScheduledJob) -> Result<Uuid, CronError> {
+        let cron = CronSchedule::parse(&schedule.cron)?;
+        schedule.next_run = cron.next_after(Utc::now());
+        let id = schedule.id;
+
+        // A re-registered schedule (e.g. one fetched via `get` and passed
+        // back in) may already carry debounce bookkeeping; seed the in-memory maps so it isn't
+        // silently dropped.
+        if !schedule.watch_observations.is_empty() {
+            self.watch_state
+                .lock()
+                .unwrap()
+                .insert(id, schedule.watch_observations.clone());
+        }
+        if !schedule.converted_files.is_empty() {
+            self.converted
+                .lock()
+                .unwrap()
+                .insert(id, schedule.converted_files.clone());
+        }
+
+        self.persist(&schedule);
+        self.parsed.lock().unwrap().insert(id, cron);
+        self.schedules.lock().unwrap().insert(id, schedule);
+        Ok(id)
+    }
+
+    /// Fetch a schedule by id
+    pub fn get(&self, id: Uuid) -> Option<ScheduledJob> {
+        self.schedules.lock().unwrap().get(&id).cloned()
+    }
+
+    /// List every registered schedule
+    pub fn list(&self) -> Vec<ScheduledJob> {
+        self.schedules.lock().unwrap().values().cloned().collect()
+    }
+
+    /// Enable or disable a schedule without losing its run history. Exposed
+    /// as a REST handler via [`super::routes::set_schedule_enabled`].
+    pub fn set_enabled(&self, id: Uuid, enabled: bool) -> Option<ScheduledJob> {
+        let updated = {
+            let mut schedules = self.schedules.lock().unwrap();
+            let schedule = schedules.get_mut(&id)?;
+            schedule.enabled = enabled;
+            schedule.clone()
+        };
+        self.persist(&updated);
+        Some(updated)
+    }
+
+    /// Remove a schedule and its debounce bookkeeping. Exposed as a REST
+    /// handler via [`super::routes::delete_schedule`].
+    pub fn remove(&self, id: Uuid) -> Option<ScheduledJob> {
+        self.parsed.lock().unwrap().remove(&id);
+        self.watch_state.lock().unwrap().remove(&id);
+        self.converted.lock().unwrap().remove(&id);
+
+        let removed = self.schedules.lock().unwrap().remove(&id);
+        if removed.is_some() {
+            if let Some(store) = &self.store {
+                let _ = store.delete_schedule(id);
+            }
+        }
+        removed
+    }
+
+    /// Evaluate every enabled schedule against `now`, enqueueing jobs for
+    /// any that are due, and return every job created this tick
+    pub fn tick(&self, now: DateTime<Utc>) -> Vec<Job> {
+        let due_ids: Vec<Uuid> = {
+            let schedules = self.schedules.lock().unwrap();
+            schedules
+                .values()
+                .filter(|s| s.enabled)
+                .filter(|s| s.next_run.is_some_and(|next| next <= now))
+                .map(|s| s.id)
+                .collect()
+        };
+
+        due_ids
+            .into_iter()
+            .flat_map(|id| self.run_schedule(id, now))
+            .collect()
+    }
+
+    fn run_schedule(&self, id: Uuid, now: DateTime<Utc>) -> Vec<Job> {
+        let Some(schedule) = self.get(id) else {
+            return Vec::new();
+        };
+
+        // The previous run hasn't reached a terminal state yet; skip this
+        // tick (no overlap) but still advance next_run so it doesn't fire
+        // again immediately once unblocked.
+        let overlapping = schedule.active_job_ids.iter().any(|active| {
+            matches!(
+                self.job_queue.get(*active).map(|j| j.status),
+                Some(JobStatus::Queued | JobStatus::Processing | JobStatus::Retrying)
+            )
+        });
+        if overlapping {
+            self.advance(id, now);
+            if let Some(updated) = self.get(id) {
+                self.persist(&updated);
+            }
+            return Vec::new();
+        }
+
+        let jobs = if let Some(dir) = schedule.watch_dir.clone() {
+            self.scan_watch_dir(id, &dir, &schedule)
+        } else if let Some(input) = &schedule.default_input {
+            vec![Job::new(input, schedule.options.clone())]
+        } else {
+            Vec::new()
+        };
+
+        for job in &jobs {
+            self.job_queue.submit(job.clone());
+        }
+
+        // A watch-dir tick can create several jobs at once; every one of
+        // them has to reach a terminal state before the next tick is
+        // allowed to run, not just the last one created.
+        let active_job_ids = if jobs.is_empty() {
+            schedule.active_job_ids.clone()
+        } else {
+            jobs.iter().map(|j| j.id).collect()
+        };
+        self.finish_run(id, now, active_job_ids);
+        jobs
+    }
+
+    fn finish_run(&self, id: Uuid, now: DateTime<Utc>, active_job_ids: Vec<Uuid>) {
+        // Fold this tick's in-memory debounce bookkeeping into the schedule
+        // before persisting it, so a restart doesn't lose "already
+        // converted" / "still being written" state.
+        let converted = self
+            .converted
+            .lock()
+            .unwrap()
+            .get(&id)
+            .cloned()
+            .unwrap_or_default();
+        let watch_observations = self
+            .watch_state
+            .lock()
+            .unwrap()
+            .get(&id)
+            .cloned()
+            .unwrap_or_default();
+
+        // Advance next_run before taking the clone below, so the persisted
+        // schedule carries the advanced value rather than the stale one that
+        // just triggered this run (otherwise a reload sees a next_run that's
+        // already <= now and fires immediately regardless of cadence).
+        self.advance(id, now);
+
+        let updated = {
+            let mut schedules = self.schedules.lock().unwrap();
+            let Some(schedule) = schedules.get_mut(&id) else {
+                return;
+            };
+            schedule.last_run = Some(now);
+            schedule.active_job_ids = active_job_ids;
+            schedule.converted_files = converted;
+            schedule.watch_observations = watch_observations;
+            schedule.clone()
+        };
+        self.persist(&updated);
+    }
+
+    fn advance(&self, id: Uuid, now: DateTime<Utc>) {
+        let Some(cron) = self.parsed.lock().unwrap().get(&id).cloned() else {
+            return;
+        };
+        let next = cron.next_after(now);
+        if let Some(schedule) = self.schedules.lock().unwrap().get_mut(&id) {
+            schedule.next_run = next;
+        }
+    }
+
+    /// Scan a watched directory for PDFs not yet converted, returning one
+    /// new job per file whose size/mtime was unchanged from the previous tick —
+    /// i.e. the file has stopped being written
+    fn scan_watch_dir(&self, id: Uuid, dir: &Path, schedule: &ScheduledJob) -> Vec<Job> {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return Vec::new();
+        };
+
+        let mut converted = self.converted.lock().unwrap();
+        let seen = converted.entry(id).or_default();
+        let mut watch_state = self.watch_state.lock().unwrap();
+        let state = watch_state.entry(id).or_default();
+
+        let mut jobs = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("pdf") || seen.contains(&path) {
+                continue;
+            }
+
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            let Ok(modified) = metadata.modified() else {
+                continue;
+            };
+            let observation = FileObservation {
+                modified,
+                len: metadata.len(),
+            };
+
+            if state.get(&path) == Some(&observation) {
+                let filename = path.file_name().and_then(|f| f.to_str()).unwrap_or_default();
+                jobs.push(Job::new(filename, schedule.options.clone()));
+                seen.insert(path.clone());
+                state.remove(&path);
+            } else {
+                state.insert(path.clone(), observation);
+            }
+        }
+
+        jobs
+    }
+
+    /// Spawn the tick loop on the current Tokio runtime, checking due
+    /// schedules every `interval`
+    pub fn spawn(self: Arc<Self>, interval: Duration) {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                self.tick(Utc::now());
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_cron_star_matches_every_field() {
+        let cron = CronSchedule::parse("* * * * *").unwrap();
+        assert!(cron.matches(Utc::now()));
+    }
+
+    #[test]
+    fn test_cron_wrong_field_count() {
+        let err = CronSchedule::parse("* * * *").unwrap_err();
+        assert!(matches!(err, CronError::WrongFieldCount(4)));
+    }
+
+    #[test]
+    fn test_cron_rejects_out_of_range_value() {
+        let err = CronSchedule::parse("60 * * * *").unwrap_err();
+        assert!(matches!(err, CronError::InvalidField { .. }));
+    }
+
+    #[test]
+    fn test_cron_step_and_range() {
+        let cron = CronSchedule::parse("*/15 9-17 * * *").unwrap();
+        assert!(cron.minute.matches(0));
+        assert!(cron.minute.matches(45));
+        assert!(!cron.minute.matches(10));
+        assert!(cron.hour.matches(9));
+        assert!(cron.hour.matches(17));
+        assert!(!cron.hour.matches(8));
+    }
+
+    #[test]
+    fn test_next_after_finds_next_matching_minute() {
+        let cron = CronSchedule::parse("30 * * * *").unwrap();
+        let after = Utc::now()
+            .with_minute(10)
+            .unwrap()
+            .with_second(0)
+            .unwrap()
+            .with_nanosecond(0)
+            .unwrap();
+
+        let next = cron.next_after(after).unwrap();
+        assert_eq!(next.minute(), 30);
+        assert!(next > after);
+    }
+
+    #[test]
+    fn test_register_computes_next_run() {
+        let scheduler = CronScheduler::new(JobQueue::new());
+        let schedule = ScheduledJob::new("* * * * *", ConvertOptions::default(), Priority::Normal)
+            .with_default_input("recurring.pdf");
+
+        let id = scheduler.register(schedule).unwrap();
+        let registered = scheduler.get(id).unwrap();
+        assert!(registered.next_run.is_some());
+    }
+
+    #[test]
+    fn test_register_rejects_invalid_cron() {
+        let scheduler = CronScheduler::new(JobQueue::new());
+        let schedule = ScheduledJob::new("not a cron", ConvertOptions::default(), Priority::Normal);
+        assert!(scheduler.register(schedule).is_err());
+    }
+
+    #[test]
+    fn test_tick_enqueues_due_schedule_and_skips_overlap() {
+        let queue = JobQueue::new();
+        let scheduler = CronScheduler::new(queue.clone());
+        let schedule = ScheduledJob::new("* * * * *", ConvertOptions::default(), Priority::Normal)
+            .with_default_input("recurring.pdf");
+        let id = scheduler.register(schedule).unwrap();
+
+        // Force the schedule due right now
+        scheduler
+            .schedules
+            .lock()
+            .unwrap()
+            .get_mut(&id)
+            .unwrap()
+            .next_run = Some(Utc::now());
+
+        let now = Utc::now();
+        let jobs = scheduler.tick(now);
+        assert_eq!(jobs.len(), 1);
+        let first_job_id = jobs[0].id;
+
+        let registered = scheduler.get(id).unwrap();
+        assert_eq!(registered.active_job_ids, vec![first_job_id]);
+        assert!(registered.next_run.unwrap() > now);
+
+        // Force due again while the first run is still Queued: must skip
+        let next_run = registered.next_run.unwrap();
+        scheduler.schedules.lock().unwrap().get_mut(&id).unwrap().next_run = Some(next_run);
+        let jobs_again = scheduler.tick(next_run);
+        assert!(jobs_again.is_empty());
+    }
+
+    #[test]
+    fn test_tick_resumes_after_previous_run_completes() {
+        let queue = JobQueue::new();
+        let scheduler = CronScheduler::new(queue.clone());
+        let schedule = ScheduledJob::new("* * * * *", ConvertOptions::default(), Priority::Normal)
+            .with_default_input("recurring.pdf");
+        let id = scheduler.register(schedule).unwrap();
+        scheduler.schedules.lock().unwrap().get_mut(&id).unwrap().next_run = Some(Utc::now());
+
+        let jobs = scheduler.tick(Utc::now());
+        queue.update(jobs[0].id, |j| j.complete(PathBuf::from("/out.pdf")));
+
+        let next_run = scheduler.get(id).unwrap().next_run.unwrap();
+        scheduler.schedules.lock().unwrap().get_mut(&id).unwrap().next_run = Some(next_run);
+        let jobs_again = scheduler.tick(next_run);
+        assert_eq!(jobs_again.len(), 1);
+    }
+
+    #[test]
+    fn test_watch_dir_debounces_file_still_being_written() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("scan.pdf");
+        std::fs::write(&file_path, b"partial").unwrap();
+
+        let queue = JobQueue::new();
+        let scheduler = CronScheduler::new(queue);
+        let schedule = ScheduledJob::new("* * * * *", ConvertOptions::default(), Priority::Normal)
+            .with_watch_dir(dir.path());
+        let id = scheduler.register(schedule).unwrap();
+        let registered = scheduler.get(id).unwrap();
+
+        // First tick only observes the file; it hasn't been stable across
+        // two ticks yet, so nothing is enqueued.
+        let first = scheduler.scan_watch_dir(id, dir.path(), &registered);
+        assert!(first.is_empty());
+
+        // Second tick with no change: the file is now considered finished.
+        let second = scheduler.scan_watch_dir(id, dir.path(), &registered);
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].input_filename, "scan.pdf");
+
+        // A third tick doesn't resubmit the same file.
+        let third = scheduler.scan_watch_dir(id, dir.path(), &registered);
+        assert!(third.is_empty());
+    }
+
+    #[test]
+    fn test_watch_dir_resets_debounce_on_change() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("growing.pdf");
+        std::fs::write(&file_path, b"partial").unwrap();
+
+        let queue = JobQueue::new();
+        let scheduler = CronScheduler::new(queue);
+        let schedule = ScheduledJob::new("* * * * *", ConvertOptions::default(), Priority::Normal)
+            .with_watch_dir(dir.path());
+        let id = scheduler.register(schedule).unwrap();
+        let registered = scheduler.get(id).unwrap();
+
+        assert!(scheduler.scan_watch_dir(id, dir.path(), &registered).is_empty());
+
+        // File still growing between ticks: still not stable.
+        std::fs::write(&file_path, b"partial plus more bytes").unwrap();
+        assert!(scheduler.scan_watch_dir(id, dir.path(), &registered).is_empty());
+
+        // Now it stops changing.
+        let jobs = scheduler.scan_watch_dir(id, dir.path(), &registered);
+        assert_eq!(jobs.len(), 1);
+    }
+
+    #[test]
+    fn test_watch_debounce_state_survives_restart() {
+        use super::super::persistence::JsonJobStore;
+
+        let store_dir = tempdir().unwrap();
+        let store: Arc<dyn JobStore> = Arc::new(JsonJobStore::new(store_dir.path()).unwrap());
+
+        let watch_dir = tempdir().unwrap();
+        let file_path = watch_dir.path().join("growing.pdf");
+        std::fs::write(&file_path, b"partial").unwrap();
+
+        let id = {
+            let scheduler = CronScheduler::with_store(JobQueue::new(), store.clone());
+            let schedule = ScheduledJob::new("* * * * *", ConvertOptions::default(), Priority::Normal)
+                .with_watch_dir(watch_dir.path());
+            let id = scheduler.register(schedule).unwrap();
+            let registered = scheduler.get(id).unwrap();
+
+            // First observation only: the file isn't stable across two
+            // ticks yet, so nothing is enqueued, but the observation needs
+            // to be persisted or a restart here would resubmit it.
+            assert!(scheduler.scan_watch_dir(id, watch_dir.path(), &registered).is_empty());
+            scheduler.finish_run(id, Utc::now(), Vec::new());
+            id
+        };
+
+        // Simulate a restart: a fresh scheduler reloads from the same store.
+        let scheduler = CronScheduler::with_store(JobQueue::new(), store);
+        let registered = scheduler.get(id).unwrap();
+        assert!(registered.watch_observations.contains_key(&file_path));
+
+        // The file hasn't changed since the observation was recorded, so
+        // this tick finally turns it into a job instead of re-observing it.
+        let jobs = scheduler.scan_watch_dir(id, watch_dir.path(), &registered);
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].input_filename, "growing.pdf");
+    }
+
+    #[test]
+    fn test_finish_run_persists_advanced_next_run_not_the_stale_one() {
+        use super::super::persistence::JsonJobStore;
+
+        let store_dir = tempdir().unwrap();
+        let store: Arc<dyn JobStore> = Arc::new(JsonJobStore::new(store_dir.path()).unwrap());
+
+        let id = {
+            let scheduler = CronScheduler::with_store(JobQueue::new(), store.clone());
+            let schedule = ScheduledJob::new("* * * * *", ConvertOptions::default(), Priority::Normal)
+                .with_default_input("recurring.pdf");
+            let id = scheduler.register(schedule).unwrap();
+
+            let before = scheduler.get(id).unwrap();
+            let stale_next_run = before.next_run.unwrap();
+
+            // Simulate the tick that's currently due: finish_run should
+            // persist next_run already moved past `now`, not the value that
+            // just triggered this run.
+            let now = stale_next_run;
+            scheduler.finish_run(id, now, Vec::new());
+
+            let in_memory = scheduler.get(id).unwrap();
+            assert!(in_memory.next_run.unwrap() > now);
+            id
+        };
+
+        // Reload from the store as a fresh process would after a restart.
+        let restarted = CronScheduler::with_store(JobQueue::new(), store);
+        let reloaded = restarted.get(id).unwrap();
+        assert!(
+            reloaded.next_run.unwrap() > Utc::now(),
+            "reloaded next_run {:?} should be in the future",
+            reloaded.next_run
+        );
+    }
+
+    #[test]
+    fn test_overlap_skip_persists_advanced_next_run() {
+        use super::super::persistence::JsonJobStore;
+
+        let store_dir = tempdir().unwrap();
+        let store: Arc<dyn JobStore> = Arc::new(JsonJobStore::new(store_dir.path()).unwrap());
+
+        let job_queue = JobQueue::new();
+        let id = {
+            let scheduler = CronScheduler::with_store(job_queue.clone(), store.clone());
+            let schedule = ScheduledJob::new("* * * * *", ConvertOptions::default(), Priority::Normal)
+                .with_default_input("recurring.pdf");
+            let id = scheduler.register(schedule).unwrap();
+
+            // Fake an in-flight run from a previous tick that hasn't
+            // reached a terminal state yet, so this tick takes the
+            // overlap-skip branch.
+            let still_running = Job::new("recurring.pdf", ConvertOptions::default());
+            let job_id = still_running.id;
+            job_queue.submit(still_running);
+            scheduler.finish_run(id, Utc::now(), vec![job_id]);
+
+            let due = scheduler.get(id).unwrap().next_run.unwrap();
+            scheduler.run_schedule(id, due);
+            id
+        };
+
+        let restarted = CronScheduler::with_store(job_queue, store);
+        let reloaded = restarted.get(id).unwrap();
+        assert!(
+            reloaded.next_run.unwrap() > Utc::now(),
+            "reloaded next_run {:?} should be in the future",
+            reloaded.next_run
+        );
+    }
+
+    #[test]
+    fn test_remove_drops_schedule_and_bookkeeping() {
+        let scheduler = CronScheduler::new(JobQueue::new());
+        let schedule = ScheduledJob::new("* * * * *", ConvertOptions::default(), Priority::Normal)
+            .with_default_input("recurring.pdf");
+        let id = scheduler.register(schedule).unwrap();
+
+        assert!(scheduler.remove(id).is_some());
+        assert!(scheduler.get(id).is_none());
+        assert!(scheduler.remove(id).is_none());
+    }
+}
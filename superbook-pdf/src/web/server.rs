@@ -0,0 +1,225 @@
+//! Web server configuration and startup
+//!
+//! [`ServerConfig`] collects the handful of settings needed to stand up
+//! [`WebServer`]: bind address/port, upload limit, and how many conversions
+//! [`Scheduler`](super::worker::Scheduler) is allowed to run at once. The REST
+//! handler functions themselves live in [`super::routes`]; binding an actual
+//! HTTP listener to them awaits a framework choice. For now `WebServer` owns
+//! the queues and starts the scheduler that dispatches jobs onto worker slots
+//! in priority order.
+
+use std::net::{AddrParseError, SocketAddr};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use super::batch::BatchQueue;
+use super::job::JobQueue;
+use super::persistence::{open_store, PersistenceConfig, RecoveryManager, StorageBackend, StoreError};
+use super::worker::Scheduler;
+use super::{DEFAULT_BIND, DEFAULT_JOB_TIMEOUT, DEFAULT_PORT, DEFAULT_UPLOAD_LIMIT};
+
+/// Configuration for the web server and its worker pool
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    pub port: u16,
+    pub bind: String,
+    pub upload_limit: usize,
+    pub job_timeout_secs: u64,
+    /// Maximum number of conversions the scheduler runs at once, default =
+    /// CPU count
+    pub workers: usize,
+    /// How jobs/batches survive a restart. Defaults to `Memory`, i.e.
+    /// nothing does; set via [`with_database_url`](Self::with_database_url)
+    /// and consumed by [`WebServer::new_with_recovery`].
+    pub persistence: PersistenceConfig,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            port: DEFAULT_PORT,
+            bind: DEFAULT_BIND.to_string(),
+            upload_limit: DEFAULT_UPLOAD_LIMIT,
+            job_timeout_secs: DEFAULT_JOB_TIMEOUT,
+            workers: num_cpus::get(),
+            persistence: PersistenceConfig::default(),
+        }
+    }
+}
+
+/// Parse a `sqlite://path` or `json://path` URL into the backend it names.
+/// A bare path with no scheme is treated as a SQLite database file, the
+/// more common case for a `DATABASE_URL`-style setting.
+fn parse_database_url(url: &str) -> StorageBackend {
+    match url.split_once("://") {
+        Some(("sqlite", path)) => StorageBackend::Sqlite(PathBuf::from(path)),
+        Some(("json", path)) => StorageBackend::Json(PathBuf::from(path)),
+        _ => StorageBackend::Sqlite(PathBuf::from(url)),
+    }
+}
+
+impl ServerConfig {
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    pub fn with_bind(mut self, bind: impl Into<String>) -> Self {
+        self.bind = bind.into();
+        self
+    }
+
+    pub fn with_upload_limit(mut self, upload_limit: usize) -> Self {
+        self.upload_limit = upload_limit;
+        self
+    }
+
+    /// Cap the scheduler's in-flight conversions at `workers`, overriding the
+    /// CPU-count default. Always at least 1.
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+
+    /// Persist jobs/batches to `url` (`sqlite://path` or `json://path`; a
+    /// bare path is treated as a SQLite database file) instead of keeping
+    /// them in memory only. Takes effect via
+    /// [`WebServer::new_with_recovery`].
+    pub fn with_database_url(mut self, url: impl Into<String>) -> Self {
+        self.persistence.backend = parse_database_url(&url.into());
+        self
+    }
+
+    /// Resolve `bind`/`port` into the address the server should listen on
+    pub fn socket_addr(&self) -> Result<SocketAddr, AddrParseError> {
+        format!("{}:{}", self.bind, self.port).parse()
+    }
+}
+
+/// Owns the job/batch queues and the scheduler that dispatches work onto
+/// worker slots
+pub struct WebServer {
+    pub config: ServerConfig,
+    pub job_queue: JobQueue,
+    pub batch_queue: BatchQueue,
+}
+
+impl WebServer {
+    pub fn new(config: ServerConfig, job_queue: JobQueue, batch_queue: BatchQueue) -> Self {
+        Self {
+            config,
+            job_queue,
+            batch_queue,
+        }
+    }
+
+    /// Build a server whose queues are backed by `config.persistence`,
+    /// reloading any jobs/batches left over from a previous run. If
+    /// `config.persistence.auto_recover` is set (the default), any job still
+    /// `Processing` — meaning no worker survived to finish it — is requeued
+    /// before the server starts accepting work.
+    pub async fn new_with_recovery(config: ServerConfig) -> Result<Self, StoreError> {
+        let store = open_store(&config.persistence)?;
+        let job_queue = JobQueue::with_store(store.clone());
+        if config.persistence.auto_recover {
+            RecoveryManager::recover(&job_queue);
+        }
+        let batch_queue = BatchQueue::with_store(job_queue.clone(), store).await;
+        Ok(Self::new(config, job_queue, batch_queue))
+    }
+
+    /// Build and spawn the priority scheduler, sized by `config.workers`,
+    /// against `work_dir`, and attach it to `job_queue` as its dispatcher so
+    /// jobs submitted through `job_queue`/`batch_queue` from here on are
+    /// scheduled rather than just sitting in memory
+    // TODO: bind an HTTP listener once a framework is chosen (see super::routes)
+    pub fn start_scheduler(&self, work_dir: std::path::PathBuf) -> Arc<Scheduler> {
+        let scheduler = Scheduler::new(self.job_queue.clone(), work_dir, self.config.workers);
+        self.job_queue.set_dispatcher(scheduler.clone());
+        scheduler.clone().spawn();
+        scheduler
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_workers_is_cpu_count() {
+        assert_eq!(ServerConfig::default().workers, num_cpus::get());
+    }
+
+    #[test]
+    fn test_with_workers_floors_at_one() {
+        let config = ServerConfig::default().with_workers(0);
+        assert_eq!(config.workers, 1);
+    }
+
+    #[test]
+    fn test_builder_chain() {
+        let config = ServerConfig::default()
+            .with_port(9000)
+            .with_bind("0.0.0.0")
+            .with_upload_limit(100 * 1024 * 1024)
+            .with_workers(4);
+
+        assert_eq!(config.port, 9000);
+        assert_eq!(config.bind, "0.0.0.0");
+        assert_eq!(config.upload_limit, 100 * 1024 * 1024);
+        assert_eq!(config.workers, 4);
+    }
+
+    #[test]
+    fn test_socket_addr_parsing() {
+        let config = ServerConfig::default().with_port(8080).with_bind("127.0.0.1");
+        let addr = config.socket_addr().unwrap();
+        assert_eq!(addr.port(), 8080);
+        assert_eq!(addr.ip().to_string(), "127.0.0.1");
+    }
+
+    #[test]
+    fn test_with_database_url_parses_scheme() {
+        let sqlite = ServerConfig::default().with_database_url("sqlite:///tmp/jobs.db");
+        assert!(matches!(sqlite.persistence.backend, StorageBackend::Sqlite(ref p) if p == std::path::Path::new("/tmp/jobs.db")));
+
+        let json = ServerConfig::default().with_database_url("json:///tmp/jobs");
+        assert!(matches!(json.persistence.backend, StorageBackend::Json(ref p) if p == std::path::Path::new("/tmp/jobs")));
+
+        let bare = ServerConfig::default().with_database_url("/tmp/bare.db");
+        assert!(matches!(bare.persistence.backend, StorageBackend::Sqlite(ref p) if p == std::path::Path::new("/tmp/bare.db")));
+    }
+
+    #[tokio::test]
+    async fn test_new_with_recovery_requeues_stuck_jobs_on_restart() {
+        use super::super::job::{ConvertOptions, Job, JobStatus};
+        use tempfile::tempdir;
+
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("jobs.db");
+
+        // First "run": a job gets stuck mid-conversion, simulating an
+        // unclean shutdown with no worker left to finish it.
+        let stuck_id = {
+            let config = ServerConfig::default()
+                .with_database_url(format!("sqlite://{}", db_path.display()));
+            let server = WebServer::new_with_recovery(config).await.unwrap();
+
+            let job = Job::new("stuck.pdf", ConvertOptions::default());
+            let job_id = job.id;
+            server.job_queue.submit(job);
+            server.job_queue.update(job_id, |j| j.start());
+            job_id
+        };
+
+        // A fresh server pointed at the same database should recover it
+        // back into the active set instead of leaving it stuck forever.
+        let config =
+            ServerConfig::default().with_database_url(format!("sqlite://{}", db_path.display()));
+        let restarted = WebServer::new_with_recovery(config).await.unwrap();
+
+        let recovered = restarted.job_queue.get(stuck_id).unwrap();
+        assert_eq!(recovered.status, JobStatus::Queued);
+        assert!(recovered.started_at.is_none());
+    }
+}
@@ -158,12 +158,21 @@ mod tests {
 
         queue.submit(job);
 
-        // Mark as failed
+        // A failure schedules a retry rather than immediately dead-lettering
         queue.update(job_id, |j: &mut Job| j.fail("Test error message".to_string()));
 
         let job = queue.get(job_id).unwrap();
-        assert_eq!(job.status, JobStatus::Failed);
+        assert_eq!(job.status, JobStatus::Retrying);
         assert_eq!(job.error, Some("Test error message".to_string()));
+        assert!(job.next_attempt_at.is_some());
+
+        // Exhausting max_attempts dead-letters the job instead
+        queue.update(job_id, |j: &mut Job| j.fail("Test error message".to_string()));
+        queue.update(job_id, |j: &mut Job| j.fail("Test error message".to_string()));
+
+        let job = queue.get(job_id).unwrap();
+        assert_eq!(job.status, JobStatus::DeadLettered);
+        assert_eq!(job.attempts, job.max_attempts);
     }
 
     // TC-WEB-011: Server config builder
@@ -320,10 +329,12 @@ mod tests {
             });
         }
 
-        // Check progress
+        // Check progress — a single failure schedules a retry rather than
+        // failing the job outright
         let progress = batch_queue.get_progress(batch_id).await.unwrap();
         assert_eq!(progress.completed, 2);
-        assert_eq!(progress.failed, 1);
+        assert_eq!(progress.retrying, 1);
+        assert_eq!(progress.failed, 0);
         assert_eq!(progress.pending, 2);
         assert_eq!(progress.total, 5);
     }